@@ -45,12 +45,16 @@ mod test_util {
                 name: Default::default(),
                 relative_path: Default::default(),
                 dependencies: Default::default(),
+                dev_dependencies: Default::default(),
                 ignored_dependencies: Default::default(),
                 ignored_private_constants: Default::default(),
                 private_constants: Default::default(),
+                public_constants: Default::default(),
+                max_todo_violations: Default::default(),
                 package_todo: Default::default(),
                 visible_to: Default::default(),
                 public_folder: Default::default(),
+                public_paths: Default::default(),
                 layer: Default::default(),
                 enforce_dependencies: Default::default(),
                 enforce_privacy: Default::default(),
@@ -58,6 +62,7 @@ mod test_util {
                 enforce_folder_privacy: Default::default(),
                 enforce_folder_visibility: None,
                 enforce_layers: Default::default(),
+                enforce_acyclic_references: Default::default(),
                 client_keys: Default::default(),
                 owner: Default::default(),
                 enforcement_globs_ignore: Default::default(),
@@ -17,11 +17,20 @@ pub(crate) mod raw_configuration;
 pub(crate) mod walk_directory;
 
 mod constant_dependencies;
+mod file_mover;
 mod file_utils;
+mod git;
+mod graph;
 mod logger;
+mod lsp;
+mod metrics;
+mod pack_renamer;
 mod pack_set;
 mod package_todo;
 mod reference_extractor;
+mod server;
+mod stats;
+mod watch;
 
 use crate::packs;
 use crate::packs::pack::write_pack_to_disk;
@@ -34,6 +43,7 @@ pub(crate) use self::parsing::process_files_with_cache;
 pub(crate) use self::parsing::ruby::experimental::get_experimental_constant_resolver;
 pub(crate) use self::parsing::ruby::zeitwerk::get_zeitwerk_constant_resolver;
 pub(crate) use self::parsing::ParsedDefinition;
+pub(crate) use self::parsing::Suppression;
 pub(crate) use self::parsing::UnresolvedReference;
 use anyhow::bail;
 pub(crate) use configuration::Configuration;
@@ -170,13 +180,29 @@ pub fn check(
 ) -> anyhow::Result<()> {
     let result = checker::check_all(configuration, files)
         .context("Failed to check files")?;
-    println!("{}", result);
+    let output = checker::reporter::get_reporter(configuration)?
+        .report_check(&result)?;
+    if !output.is_empty() {
+        println!("{}", output);
+    }
     if result.has_violations() {
         bail!("Violations found!")
     }
     Ok(())
 }
 
+pub fn watch(configuration: Configuration) -> anyhow::Result<()> {
+    watch::watch(configuration)
+}
+
+pub fn run_language_server(configuration: Configuration) -> anyhow::Result<()> {
+    lsp::run(configuration)
+}
+
+pub fn run_server(configuration: Configuration) -> anyhow::Result<()> {
+    server::run(configuration)
+}
+
 pub fn update(configuration: &Configuration) -> anyhow::Result<()> {
     checker::update(configuration)
 }
@@ -197,7 +223,7 @@ pub fn add_dependency(
         .context(format!("`{}` not found", to))?;
 
     // Print a warning if the dependency already exists
-    if from_pack.dependencies.contains(&to_pack.name) {
+    if from_pack.depends_on(&to_pack.name) {
         println!(
             "`{}` already depends on `{}`!",
             from_pack.name, to_pack.name
@@ -226,6 +252,107 @@ pub fn add_dependency(
     Ok(())
 }
 
+pub fn move_files(
+    configuration: &Configuration,
+    paths: Vec<String>,
+    destination: String,
+) -> anyhow::Result<()> {
+    file_mover::move_files(configuration, paths, destination)
+}
+
+pub fn rename_pack(
+    configuration: &Configuration,
+    old_name: String,
+    new_name: String,
+) -> anyhow::Result<()> {
+    pack_renamer::rename_pack(configuration, old_name, new_name)
+}
+
+pub fn graph(
+    configuration: &Configuration,
+    format: graph::GraphFormat,
+    implicit: bool,
+    focus: Option<String>,
+    depth: usize,
+) -> anyhow::Result<()> {
+    let output = graph::graph(
+        configuration,
+        graph::GraphOptions {
+            format,
+            implicit,
+            focus,
+            depth,
+        },
+    )?;
+    print!("{}", output);
+    Ok(())
+}
+
+pub fn list_expiring_todos(
+    configuration: &Configuration,
+    within_days: i64,
+) -> anyhow::Result<()> {
+    let today = package_todo::today();
+    let until = today + chrono::Duration::days(within_days);
+    let entries = package_todo::expiring_entries(configuration, until);
+    if entries.is_empty() {
+        println!(
+            "No package_todo.yml entries expire within {} days",
+            within_days
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} package_todo.yml entries expire within {} days:",
+        entries.len(),
+        within_days
+    );
+    for entry in entries {
+        let mut line = format!(
+            "{}: `{}` on `{}` {} {}",
+            entry
+                .responsible_pack
+                .relative_path
+                .join("package_todo.yml")
+                .display(),
+            entry.constant_name,
+            entry.defining_pack_name,
+            if entry.expires <= today {
+                "expired on"
+            } else {
+                "expires on"
+            },
+            entry.expires
+        );
+        if let Some(owner) = &entry.violation_group.owner {
+            line.push_str(&format!(", owner: {}", owner));
+        }
+        if let Some(ticket) = &entry.violation_group.ticket {
+            line.push_str(&format!(", ticket: {}", ticket));
+        }
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+pub fn metrics(
+    configuration: &Configuration,
+    format: metrics::MetricsFormat,
+) -> anyhow::Result<()> {
+    println!("{}", metrics::metrics(configuration, format)?);
+    Ok(())
+}
+
+pub fn stats(
+    configuration: &Configuration,
+    format: stats::StatsFormat,
+    git_history: Option<usize>,
+) -> anyhow::Result<()> {
+    println!("{}", stats::stats(configuration, format, git_history)?);
+    Ok(())
+}
+
 pub fn list_included_files(configuration: Configuration) -> anyhow::Result<()> {
     configuration
         .included_files
@@ -314,9 +441,15 @@ pub struct ProcessedFile {
     pub absolute_path: PathBuf,
     pub unresolved_references: Vec<UnresolvedReference>,
     pub definitions: Vec<ParsedDefinition>,
+    // Every `pks:disable-next-line` comment in the file, including those
+    // that don't apply to any reference
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<Suppression>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Eq, Clone)]
+#[derive(
+    Debug, PartialEq, Serialize, Deserialize, Default, Eq, Clone, Hash,
+)]
 pub struct SourceLocation {
     line: usize,
     column: usize,
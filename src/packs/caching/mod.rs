@@ -5,6 +5,11 @@ pub(crate) mod cache;
 pub(crate) mod noop_cache;
 pub(crate) mod per_file_cache;
 
+// Bumped whenever a change to `ProcessedFile` makes entries written by older
+// versions wrong, so they're not served from the cache after an upgrade.
+// Each version gets its own directory.
+pub(crate) const CACHE_SCHEMA_VERSION: usize = 3;
+
 pub enum CacheResult {
     Processed(ProcessedFile),
     Miss(EmptyCacheEntry),
@@ -38,6 +43,10 @@ impl EmptyCacheEntry {
     }
 }
 
+pub(crate) fn versioned_cache_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join(format!("v{}", CACHE_SCHEMA_VERSION))
+}
+
 pub fn create_cache_dir_idempotently(cache_dir: &Path) {
     std::fs::create_dir_all(cache_dir)
         .expect("Failed to create cache directory");
@@ -168,9 +168,11 @@ mod tests {
                         start_col: 22,
                         end_row: 8,
                         end_col: 25,
-                    }
+                    },
+                    suppressions: vec![],
                 }],
                 definitions: vec![],
+                suppressions: vec![],
             }
         };
 
@@ -1,13 +1,17 @@
 // Module declarations
+mod acyclic_references;
 mod dependency;
 pub(crate) mod layer;
 
 mod common_test;
+mod cycles;
 mod folder_privacy;
-mod output_helper;
+pub(crate) mod forbidden_dependency;
 pub(crate) mod pack_checker;
 mod privacy;
 pub(crate) mod reference;
+pub(crate) mod reporter;
+mod todo_budget;
 mod visibility;
 
 // Internal imports
@@ -15,6 +19,7 @@ use crate::packs::pack::write_pack_to_disk;
 use crate::packs::pack::Pack;
 use crate::packs::package_todo;
 use crate::packs::Configuration;
+use crate::packs::SourceLocation;
 
 use anyhow::bail;
 // External imports
@@ -22,17 +27,18 @@ use rayon::prelude::IntoParallelIterator;
 use rayon::prelude::IntoParallelRefIterator;
 use rayon::prelude::ParallelIterator;
 use reference::Reference;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::fmt;
-use std::fmt::Display;
-use std::fmt::Formatter;
 use std::{collections::HashSet, path::PathBuf};
+pub(crate) use todo_budget::ExceededTodoBudget;
 use tracing::debug;
 
-use super::bin_locater;
-use super::reference_extractor::get_all_references;
+use super::git;
+use super::reference_extractor::{
+    get_all_references, get_all_references_and_suppressions, FileSuppression,
+};
 
-#[derive(PartialEq, Clone, Eq, Hash, Debug)]
+#[derive(PartialEq, Clone, Eq, Hash, Debug, Serialize)]
 pub struct ViolationIdentifier {
     pub violation_type: String,
     pub strict: bool,
@@ -45,6 +51,69 @@ pub struct ViolationIdentifier {
 pub struct Violation {
     message: String,
     pub identifier: ViolationIdentifier,
+    pub source_location: SourceLocation,
+    pub relative_defining_file: Option<String>,
+}
+
+// A `pks:disable-next-line` comment listing violation types that no longer
+// occur on the next line
+#[derive(PartialEq, Clone, Eq, Hash, Debug, Serialize)]
+pub struct UnusedSuppression {
+    pub file: String,
+    pub line: usize,
+    pub violation_types: Vec<String>,
+}
+
+impl UnusedSuppression {
+    pub(crate) fn message(&self) -> String {
+        format!(
+            "Unused suppression: no {} violation on the line after `pks:disable-next-line {}`",
+            self.violation_types.join(" or "),
+            self.violation_types.join(", ")
+        )
+    }
+}
+
+// (file, line of the comment, violation type)
+type SuppressionKey = (String, usize, String);
+
+impl Violation {
+    // The message without ANSI colors
+    pub(crate) fn plain_message(&self) -> String {
+        let stripped_message = strip_ansi_escapes::strip(&self.message);
+        String::from_utf8_lossy(&stripped_message).to_string()
+    }
+}
+
+fn sorted_violations<'a>(
+    violations: impl IntoIterator<Item = &'a Violation>,
+) -> Vec<&'a Violation> {
+    let mut sorted: Vec<&Violation> = violations.into_iter().collect();
+    sorted.sort_by(|a, b| {
+        a.identifier
+            .file
+            .cmp(&b.identifier.file)
+            .then_with(|| a.source_location.line.cmp(&b.source_location.line))
+            .then_with(|| {
+                a.source_location.column.cmp(&b.source_location.column)
+            })
+            .then_with(|| a.message.cmp(&b.message))
+    });
+    sorted
+}
+
+fn sorted_identifiers<'a>(
+    identifiers: impl IntoIterator<Item = &'a ViolationIdentifier>,
+) -> Vec<&'a ViolationIdentifier> {
+    let mut sorted: Vec<&ViolationIdentifier> =
+        identifiers.into_iter().collect();
+    sorted.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.constant_name.cmp(&b.constant_name))
+            .then_with(|| a.violation_type.cmp(&b.violation_type))
+    });
+    sorted
 }
 
 pub(crate) trait CheckerInterface {
@@ -59,62 +128,40 @@ pub(crate) trait CheckerInterface {
 
 pub(crate) trait ValidatorInterface {
     fn validate(&self, configuration: &Configuration) -> Option<Vec<String>>;
+
+    // Problems that are reported by `validate` without making it fail
+    fn warn(&self, _configuration: &Configuration) -> Option<Vec<String>> {
+        None
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct CheckAllResult {
     reportable_violations: HashSet<Violation>,
     stale_violations: Vec<ViolationIdentifier>,
-    strict_mode_violations: Vec<ViolationIdentifier>,
+    strict_mode_violations: Vec<Violation>,
+    // Found violations that are already in package_todo.yml files
+    recorded_violations: Vec<Violation>,
+    // Only populated with `--report-unused-disables`
+    unused_suppressions: Vec<UnusedSuppression>,
+    // Packs recording more violations than their `max_todo_violations`
+    exceeded_todo_budgets: Vec<ExceededTodoBudget>,
 }
 
 impl CheckAllResult {
+    pub(crate) fn reportable_violations(&self) -> &HashSet<Violation> {
+        &self.reportable_violations
+    }
+
     pub fn has_violations(&self) -> bool {
         !self.reportable_violations.is_empty()
             || !self.stale_violations.is_empty()
             || !self.strict_mode_violations.is_empty()
-    }
-
-    fn write_violations(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        if !self.reportable_violations.is_empty() {
-            let mut sorted_violations: Vec<&Violation> =
-                self.reportable_violations.iter().collect();
-            sorted_violations.sort_by(|a, b| a.message.cmp(&b.message));
-
-            writeln!(f, "{} violation(s) detected:", sorted_violations.len())?;
-
-            for violation in sorted_violations {
-                writeln!(f, "{}\n", violation.message)?;
-            }
-        }
-
-        if !self.stale_violations.is_empty() {
-            writeln!(
-                f,
-                "There were stale violations found, please run `{} update`",
-                bin_locater::packs_bin_name(),
-            )?;
-        }
-
-        if !self.strict_mode_violations.is_empty() {
-            for v in self.strict_mode_violations.iter() {
-                let error_message = build_strict_violation_message(v);
-                writeln!(f, "{}", error_message)?;
-            }
-        }
-        Ok(())
+            || !self.unused_suppressions.is_empty()
+            || !self.exceeded_todo_budgets.is_empty()
     }
 }
 
-impl Display for CheckAllResult {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        if self.has_violations() {
-            self.write_violations(f)
-        } else {
-            write!(f, "No violations detected!")
-        }
-    }
-}
 struct CheckAllBuilder<'a> {
     configuration: &'a Configuration,
     found_violations: &'a FoundViolations,
@@ -122,7 +169,10 @@ struct CheckAllBuilder<'a> {
 #[derive(Debug)]
 struct FoundViolations {
     absolute_paths: HashSet<PathBuf>,
+    // Relative paths of files deleted since `configuration.changed_since`
+    deleted_files: HashSet<String>,
     violations: HashSet<Violation>,
+    unused_suppressions: Vec<UnusedSuppression>,
 }
 
 impl<'a> CheckAllBuilder<'a> {
@@ -141,9 +191,9 @@ impl<'a> CheckAllBuilder<'a> {
 
         Ok(CheckAllResult {
             reportable_violations: self
-                .build_reportable_violations(recorded_violations)
+                .build_reportable_violations()
                 .into_iter()
-                .cloned()
+                .map(|violation| self.note_expired_todo(violation))
                 .collect(),
             stale_violations: self
                 .build_stale_violations(recorded_violations)?
@@ -155,13 +205,54 @@ impl<'a> CheckAllBuilder<'a> {
                 .into_iter()
                 .cloned()
                 .collect(),
+            recorded_violations: self
+                .build_recorded_violations()
+                .into_iter()
+                .cloned()
+                .collect(),
+            unused_suppressions: self
+                .found_violations
+                .unused_suppressions
+                .clone(),
+            exceeded_todo_budgets: self.build_exceeded_todo_budgets(),
         })
     }
 
-    fn build_reportable_violations(
-        &mut self,
-        recorded_violations: &HashSet<ViolationIdentifier>,
-    ) -> HashSet<&'a Violation> {
+    // Only the budgets of packs whose files were checked, so checking a few
+    // files doesn't fail because of an unrelated pack
+    fn build_exceeded_todo_budgets(&self) -> Vec<ExceededTodoBudget> {
+        let pack_set = &self.configuration.pack_set;
+        let checked_pack_names: HashSet<&str> = self
+            .found_violations
+            .absolute_paths
+            .iter()
+            .filter_map(|path| pack_set.for_file(path).ok().flatten())
+            .map(|pack| pack.name.as_str())
+            .collect();
+        todo_budget::exceeded_todo_budgets(self.configuration)
+            .into_iter()
+            .filter(|budget| {
+                checked_pack_names.contains(budget.pack_name.as_str())
+            })
+            .collect()
+    }
+
+    fn build_recorded_violations(&self) -> Vec<&'a Violation> {
+        if self.configuration.ignore_recorded_violations {
+            return vec![];
+        }
+        let pack_set = &self.configuration.pack_set;
+        self.found_violations
+            .violations
+            .iter()
+            .filter(|v| {
+                !v.identifier.strict && pack_set.is_recorded(&v.identifier)
+            })
+            .collect()
+    }
+
+    fn build_reportable_violations(&mut self) -> HashSet<&'a Violation> {
+        let pack_set = &self.configuration.pack_set;
         let reportable_violations =
             if self.configuration.ignore_recorded_violations {
                 debug!("Filtering recorded violations is disabled in config");
@@ -170,12 +261,29 @@ impl<'a> CheckAllBuilder<'a> {
                 self.found_violations
                     .violations
                     .iter()
-                    .filter(|v| !recorded_violations.contains(&v.identifier))
+                    .filter(|v| !pack_set.is_recorded(&v.identifier))
                     .collect()
             };
         reportable_violations
     }
 
+    fn note_expired_todo(&self, violation: &Violation) -> Violation {
+        let expired_violations =
+            &self.configuration.pack_set.expired_violations;
+        match expired_violations.get(&violation.identifier) {
+            Some(expires) if !self.configuration.ignore_recorded_violations => {
+                Violation {
+                    message: format!(
+                        "{}\nIts package_todo.yml entry expired on {}",
+                        violation.message, expires
+                    ),
+                    ..violation.clone()
+                }
+            }
+            _ => violation.clone(),
+        }
+    }
+
     fn build_stale_violations(
         &mut self,
         recorded_violations: &'a HashSet<ViolationIdentifier>,
@@ -211,8 +319,18 @@ impl<'a> CheckAllBuilder<'a> {
             })
             .collect::<anyhow::Result<HashSet<&str>>>()?;
 
+        let deleted_files = &self.found_violations.deleted_files;
+        // When only changed files are checked, recorded violations in
+        // untouched files can't have gone stale
+        let only_changed_files = self.configuration.changed_since.is_some();
+
         let stale_violations = recorded_violations
             .par_iter()
+            .filter(|v_identifier| {
+                !only_changed_files
+                    || relative_files.contains(v_identifier.file.as_str())
+                    || deleted_files.contains(&v_identifier.file)
+            })
             .filter(|v_identifier| {
                 Self::is_stale_violation(
                     &relative_files,
@@ -238,45 +356,91 @@ impl<'a> CheckAllBuilder<'a> {
         }
     }
 
-    fn build_strict_mode_violations(&self) -> Vec<&'a ViolationIdentifier> {
+    fn build_strict_mode_violations(&self) -> Vec<&'a Violation> {
         self.found_violations
             .violations
             .iter()
             .filter(|v| v.identifier.strict)
-            .map(|v| &v.identifier)
             .collect()
     }
 }
 
+// Builds the result of `check_all` from violations that were already found
+// in `absolute_paths` (e.g. kept in memory by `check --watch`)
+pub(crate) fn check_result_for_violations(
+    configuration: &Configuration,
+    absolute_paths: HashSet<PathBuf>,
+    violations: HashSet<Violation>,
+) -> anyhow::Result<CheckAllResult> {
+    let found_violations = FoundViolations {
+        absolute_paths,
+        deleted_files: HashSet::new(),
+        violations,
+        unused_suppressions: Vec::new(),
+    };
+    CheckAllBuilder::new(configuration, &found_violations).build()
+}
+
 pub(crate) fn check_all(
     configuration: &Configuration,
     files: Vec<String>,
 ) -> anyhow::Result<CheckAllResult> {
     let checkers = get_checkers(configuration);
 
-    debug!("Intersecting input files with configuration included files");
-    let absolute_paths: HashSet<PathBuf> =
-        configuration.intersect_files(files.clone());
+    let (absolute_paths, deleted_files) = match &configuration.changed_since {
+        Some(git_ref) => {
+            debug!("Finding files changed since {}", git_ref);
+            let changed_files = git::changed_files_since(
+                &configuration.absolute_root,
+                git_ref,
+            )?;
+            let absolute_paths = if changed_files.changed.is_empty() {
+                HashSet::new()
+            } else {
+                configuration.intersect_files(changed_files.changed)
+            };
+            (absolute_paths, changed_files.deleted.into_iter().collect())
+        }
+        None => {
+            debug!(
+                "Intersecting input files with configuration included files"
+            );
+            (configuration.intersect_files(files.clone()), HashSet::new())
+        }
+    };
 
-    let violations: HashSet<Violation> =
+    let (violations, unused_suppressions) =
         get_all_violations(configuration, &absolute_paths, &checkers)?;
     let found_violations = FoundViolations {
         absolute_paths,
+        deleted_files,
         violations,
+        unused_suppressions: if configuration.report_unused_disables {
+            unused_suppressions
+        } else {
+            vec![]
+        },
     };
     CheckAllBuilder::new(configuration, &found_violations).build()
 }
 
-fn validate(configuration: &Configuration) -> Vec<String> {
-    debug!("Running validators against packages");
-    let validators: Vec<Box<dyn ValidatorInterface + Send + Sync>> = vec![
+fn validators(
+    configuration: &Configuration,
+) -> Vec<Box<dyn ValidatorInterface + Send + Sync>> {
+    vec![
         Box::new(dependency::Checker {}),
         Box::new(layer::Checker {
             layers: configuration.layers.clone(),
         }),
-    ];
+        Box::new(acyclic_references::Validator {}),
+        Box::new(forbidden_dependency::Checker {}),
+        Box::new(privacy::Checker {}),
+    ]
+}
 
-    let mut validation_errors: Vec<String> = validators
+fn validate(configuration: &Configuration) -> Vec<String> {
+    debug!("Running validators against packages");
+    let mut validation_errors: Vec<String> = validators(configuration)
         .iter()
         .filter_map(|v| v.validate(configuration))
         .flatten()
@@ -297,9 +461,25 @@ pub(crate) fn build_strict_violation_message(
     violation_identifier.violation_type,)
 }
 
+fn validation_warnings(configuration: &Configuration) -> Vec<String> {
+    validators(configuration)
+        .iter()
+        .filter_map(|v| v.warn(configuration))
+        .flatten()
+        .collect()
+}
+
 pub(crate) fn validate_all(
     configuration: &Configuration,
 ) -> anyhow::Result<()> {
+    let validation_warnings = validation_warnings(configuration);
+    if !validation_warnings.is_empty() {
+        println!("{} validation warning(s):", validation_warnings.len());
+        for validation_warning in validation_warnings.iter() {
+            println!("{}\n", validation_warning);
+        }
+    }
+
     let validation_errors = validate(configuration);
     if !validation_errors.is_empty() {
         println!("{} validation error(s) detected:", validation_errors.len());
@@ -317,30 +497,45 @@ pub(crate) fn validate_all(
 pub(crate) fn update(configuration: &Configuration) -> anyhow::Result<()> {
     let checkers = get_checkers(configuration);
 
-    let violations = get_all_violations(
+    let (violations, _) = get_all_violations(
         configuration,
         &configuration.included_files,
         &checkers,
     )?;
 
-    let strict_violations = &violations
-        .iter()
-        .filter(|v| v.identifier.strict)
-        .collect::<Vec<&Violation>>();
-    if !strict_violations.is_empty() {
-        for violation in strict_violations {
-            let strict_message =
-                build_strict_violation_message(&violation.identifier);
-            println!("{}", strict_message);
-        }
-        println!(
-            "{} strict mode violation(s) detected. These violations must be fixed for `check` to succeed.",
-            &strict_violations.len()
-        );
-    }
-    package_todo::write_violations_to_disk(configuration, violations);
-    println!("Successfully updated package_todo.yml files!");
+    let (strict_mode_violations, recordable_violations): (Vec<_>, Vec<_>) =
+        violations
+            .into_iter()
+            .partition(|v: &Violation| v.identifier.strict);
+    // With `todo_ratchet`, package_todo.yml files only keep the violations
+    // they already record
+    let (recorded_violations, unrecorded_violations): (Vec<_>, Vec<_>) =
+        recordable_violations.into_iter().partition(|v| {
+            !configuration.todo_ratchet
+                || configuration
+                    .pack_set
+                    .all_violations
+                    .contains(&v.identifier)
+        });
+    let result = CheckAllResult {
+        reportable_violations: unrecorded_violations.into_iter().collect(),
+        stale_violations: Vec::new(),
+        strict_mode_violations,
+        recorded_violations,
+        unused_suppressions: Vec::new(),
+        exceeded_todo_budgets: Vec::new(),
+    };
+    let output =
+        reporter::get_reporter(configuration)?.report_update(&result)?;
+    package_todo::write_violations_to_disk(
+        configuration,
+        result.recorded_violations.iter().cloned().collect(),
+    );
+    println!("{}", output);
 
+    if !result.reportable_violations.is_empty() {
+        bail!("New violations found!")
+    }
     Ok(())
 }
 
@@ -407,9 +602,17 @@ fn get_unnecessary_dependencies(
         HashMap::new();
     for pack in &configuration.pack_set.packs {
         for dependency_name in &pack.dependencies {
-            let edge_key = (pack.name.clone(), dependency_name.clone());
-            let edge_count = edge_counts.get(&edge_key).unwrap_or(&0);
-            if edge_count == &0 {
+            // A glob is needed while any other pack it matches is referenced
+            let referenced = configuration
+                .pack_set
+                .matching_packs(dependency_name)
+                .iter()
+                .filter(|to_pack| to_pack.name != pack.name)
+                .any(|to_pack| {
+                    let edge_key = (pack.name.clone(), to_pack.name.clone());
+                    edge_counts.get(&edge_key).unwrap_or(&0) > &0
+                });
+            if !referenced {
                 unnecessary_dependencies
                     .entry(pack.clone())
                     .or_default()
@@ -425,31 +628,109 @@ fn get_all_violations(
     configuration: &Configuration,
     absolute_paths: &HashSet<PathBuf>,
     checkers: &Vec<Box<dyn CheckerInterface + Send + Sync>>,
+) -> anyhow::Result<(HashSet<Violation>, Vec<UnusedSuppression>)> {
+    let (references, suppressions) =
+        get_all_references_and_suppressions(configuration, absolute_paths)?;
+    let (violations, used_suppressions) = check_references_and_suppressions(
+        configuration,
+        &references,
+        checkers,
+    )?;
+    Ok((
+        violations,
+        unused_suppressions(suppressions, &used_suppressions),
+    ))
+}
+
+// Violations disabled by a `pks:disable-next-line` comment are left out
+pub(crate) fn check_references(
+    configuration: &Configuration,
+    references: &[Reference],
+    checkers: &Vec<Box<dyn CheckerInterface + Send + Sync>>,
 ) -> anyhow::Result<HashSet<Violation>> {
-    let references = get_all_references(configuration, absolute_paths)?;
+    let (violations, _) =
+        check_references_and_suppressions(configuration, references, checkers)?;
+    Ok(violations)
+}
+
+fn check_references_and_suppressions(
+    configuration: &Configuration,
+    references: &[Reference],
+    checkers: &Vec<Box<dyn CheckerInterface + Send + Sync>>,
+) -> anyhow::Result<(HashSet<Violation>, HashSet<SuppressionKey>)> {
     debug!("Running checkers on resolved references");
 
-    let violations = checkers
+    let result = checkers
         .into_par_iter()
-        .try_fold(HashSet::new, |mut acc, c| {
-            for reference in &references {
-                if let Some(violation) = c.check(reference, configuration)? {
-                    acc.insert(violation);
+        .try_fold(
+            || (HashSet::new(), HashSet::new()),
+            |(mut violations, mut used_suppressions), c| {
+                for reference in references {
+                    let Some(violation) = c.check(reference, configuration)?
+                    else {
+                        continue;
+                    };
+                    let violation_type = &violation.identifier.violation_type;
+                    match reference.suppression_for(violation_type) {
+                        Some(suppression) => {
+                            used_suppressions.insert((
+                                reference.relative_referencing_file.clone(),
+                                suppression.line,
+                                violation_type.clone(),
+                            ));
+                        }
+                        None => {
+                            violations.insert(violation);
+                        }
+                    }
                 }
-            }
-            Ok(acc)
-        })
-        .try_reduce(HashSet::new, |mut acc, v| {
-            acc.extend(v);
-            Ok(acc)
-        });
+                Ok((violations, used_suppressions))
+            },
+        )
+        .try_reduce(
+            || (HashSet::new(), HashSet::new()),
+            |(mut violations, mut used_suppressions), (v, u)| {
+                violations.extend(v);
+                used_suppressions.extend(u);
+                Ok((violations, used_suppressions))
+            },
+        );
 
     debug!("Finished running checkers");
 
-    violations
+    result
+}
+
+fn unused_suppressions(
+    suppressions: Vec<FileSuppression>,
+    used_suppressions: &HashSet<SuppressionKey>,
+) -> Vec<UnusedSuppression> {
+    let mut unused: Vec<UnusedSuppression> = suppressions
+        .into_iter()
+        .filter_map(|(file, suppression)| {
+            let violation_types: Vec<String> = suppression
+                .violation_types
+                .into_iter()
+                .filter(|violation_type| {
+                    !used_suppressions.contains(&(
+                        file.clone(),
+                        suppression.line,
+                        violation_type.clone(),
+                    ))
+                })
+                .collect();
+            (!violation_types.is_empty()).then_some(UnusedSuppression {
+                file,
+                line: suppression.line,
+                violation_types,
+            })
+        })
+        .collect();
+    unused.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    unused
 }
 
-fn get_checkers(
+pub(crate) fn get_checkers(
     configuration: &Configuration,
 ) -> Vec<Box<dyn CheckerInterface + Send + Sync>> {
     vec![
@@ -460,6 +741,7 @@ fn get_checkers(
             layers: configuration.layers.clone(),
         }),
         Box::new(folder_privacy::Checker {}),
+        Box::new(forbidden_dependency::Checker {}),
     ]
 }
 
@@ -478,54 +760,3 @@ fn remove_reference_to_dependency(
     write_pack_to_disk(&updated_pack)?;
     Ok(())
 }
-#[cfg(test)]
-mod tests {
-    use crate::packs::checker::{
-        CheckAllResult, Violation, ViolationIdentifier,
-    };
-
-    #[test]
-    fn test_write_violations() {
-        let chec_result = CheckAllResult {
-            reportable_violations: vec![
-                Violation {
-                    message: "foo/bar/file1.rb:10:5\nPrivacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`".to_string(),
-                    identifier: ViolationIdentifier {
-                        violation_type: "Privacy".to_string(),
-                        strict: false,
-                        file: "foo/bar/file1.rb".to_string(),
-                        constant_name: "::Foo::PrivateClass".to_string(),
-                        referencing_pack_name: "bar".to_string(),
-                        defining_pack_name: "foo".to_string(),
-                    }
-                },
-                Violation {
-                    message: "foo/bar/file2.rb:15:3\nDependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`".to_string(),
-                    identifier: ViolationIdentifier {
-                        violation_type: "Dependency".to_string(),
-                        strict: false,
-                        file: "foo/bar/file2.rb".to_string(),
-                        constant_name: "::Foo::AnotherClass".to_string(),
-                        referencing_pack_name: "foo".to_string(),
-                        defining_pack_name: "bar".to_string(),
-                    }
-                }
-            ].iter().cloned().collect(),
-            stale_violations: Vec::new(),
-            strict_mode_violations: Vec::new(),
-        };
-
-        let expected_output = "2 violation(s) detected:
-foo/bar/file1.rb:10:5
-Privacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`
-
-foo/bar/file2.rb:15:3
-Dependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`
-
-";
-
-        let actual = format!("{}", chec_result);
-
-        assert_eq!(actual, expected_output);
-    }
-}
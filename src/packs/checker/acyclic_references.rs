@@ -0,0 +1,150 @@
+// Finds cycles of packs that reference each other's constants, whatever their
+// declared dependencies say. Violations recorded in package_todo.yml files
+// count as references, so recording a violation doesn't hide a cycle.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use petgraph::algo::tarjan_scc;
+use petgraph::prelude::{DiGraph, NodeIndex};
+
+use super::cycles::{count_references, Component};
+use super::ValidatorInterface;
+use crate::packs::Configuration;
+
+pub struct Validator {}
+
+impl ValidatorInterface for Validator {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let enforcing_packs: HashSet<&str> = configuration
+            .pack_set
+            .packs
+            .iter()
+            .filter(|pack| {
+                pack.enforce_acyclic_references
+                    .as_ref()
+                    .is_some_and(|setting| !setting.is_false())
+            })
+            .map(|pack| pack.name.as_str())
+            .collect();
+        // Finding references means parsing the whole project
+        if enforcing_packs.is_empty() {
+            return None;
+        }
+
+        let mut reference_counts = match count_references(configuration) {
+            Ok(reference_counts) => reference_counts,
+            Err(error) => {
+                return Some(vec![format!(
+                    "Failed to count references between packs: {:#}",
+                    error
+                )])
+            }
+        };
+        for pack in &configuration.pack_set.packs {
+            for defining_pack_name in
+                pack.package_todo.violations_by_defining_pack.keys()
+            {
+                reference_counts
+                    .entry((pack.name.clone(), defining_pack_name.clone()))
+                    .or_default();
+            }
+        }
+        let edges: BTreeMap<(&str, &str), usize> = reference_counts
+            .iter()
+            .filter(|((from, to), _)| from != to)
+            .map(|((from, to), count)| ((from.as_str(), to.as_str()), *count))
+            .collect();
+
+        let mut graph = DiGraph::<&str, ()>::new();
+        let mut nodes: HashMap<&str, NodeIndex> = HashMap::new();
+        for (from, to) in edges.keys() {
+            let from_node =
+                *nodes.entry(from).or_insert_with(|| graph.add_node(from));
+            let to_node =
+                *nodes.entry(to).or_insert_with(|| graph.add_node(to));
+            graph.add_edge(from_node, to_node, ());
+        }
+
+        let mut descriptions = vec![];
+        for component in tarjan_scc(&graph) {
+            let mut pack_names: Vec<&str> =
+                component.iter().map(|node| graph[*node]).collect();
+            if pack_names.len() < 2
+                || !pack_names.iter().any(|name| enforcing_packs.contains(name))
+            {
+                continue;
+            }
+            pack_names.sort();
+            let component_edges = edges
+                .iter()
+                .filter(|((from, to), _)| {
+                    pack_names.contains(from) && pack_names.contains(to)
+                })
+                .map(|(edge, count)| (*edge, *count))
+                .collect();
+            let component = Component::new(component_edges);
+
+            let mut description = format!(
+                "{}\nCycles, with the number of references from each pack to the next:",
+                pack_names.join(", ")
+            );
+            for cycle in component.cycles() {
+                if cycle.iter().any(|name| enforcing_packs.contains(name)) {
+                    description.push_str("\n- ");
+                    description.push_str(&component.describe_cycle(&cycle));
+                }
+            }
+            descriptions.push(description);
+        }
+
+        if descriptions.is_empty() {
+            return None;
+        }
+        Some(vec![format!(
+            "Found {} reference cycle(s) involving packs with `enforce_acyclic_references`:\n\n{}",
+            descriptions.len(),
+            descriptions.join("\n\n")
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::configuration;
+    use crate::packs::pack::CheckerSetting;
+    use std::path::PathBuf;
+
+    fn reference_cycles_configuration() -> Configuration {
+        configuration::get(
+            PathBuf::from("tests/fixtures/app_with_reference_cycles")
+                .canonicalize()
+                .expect("Could not canonicalize path")
+                .as_path(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_with_reference_cycle() {
+        let error = Validator {}.validate(&reference_cycles_configuration());
+        assert_eq!(
+            error,
+            Some(vec![String::from(
+                "Found 1 reference cycle(s) involving packs with `enforce_acyclic_references`:
+
+packs/bar, packs/foo
+Cycles, with the number of references from each pack to the next:
+- packs/bar -> packs/foo (1) -> packs/bar (2)"
+            )])
+        );
+    }
+
+    #[test]
+    fn test_validate_without_enforcing_packs() {
+        let mut configuration = reference_cycles_configuration();
+        for pack in configuration.pack_set.packs.iter_mut() {
+            pack.enforce_acyclic_references = Some(CheckerSetting::False);
+        }
+        assert_eq!(Validator {}.validate(&configuration), None);
+    }
+}
@@ -54,6 +54,10 @@ pub mod tests {
                 referencing_pack_name: String::from("packs/foo"),
                 defining_pack_name: String::from("packs/bar"),
             },
+            source_location: SourceLocation { line: 3, column: 1 },
+            relative_defining_file: Some(String::from(
+                "packs/bar/app/services/public/bar.rb",
+            )),
         }
     }
 
@@ -111,6 +115,7 @@ pub mod tests {
                 "packs/bar/app/services/public/bar.rb",
             )),
             source_location: SourceLocation { line: 3, column: 1 },
+            suppressions: vec![],
         });
 
         let root_pack = Pack {
@@ -0,0 +1,244 @@
+// Explains a group of packs that depend on each other (a strongly connected
+// component): every dependency in it is shown in its shortest cycle, and the
+// dependencies backed by the fewest references are suggested for removal.
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use crate::packs::reference_extractor::get_all_references;
+use crate::packs::Configuration;
+
+// A dependency, from the depending pack to the pack depended on
+pub(super) type Edge<'a> = (&'a str, &'a str);
+
+pub(super) struct Component<'a> {
+    // The number of references from the first pack to constants of the
+    // second, for every dependency within the component
+    edges: BTreeMap<Edge<'a>, usize>,
+}
+
+impl<'a> Component<'a> {
+    pub(super) fn new(edges: BTreeMap<Edge<'a>, usize>) -> Self {
+        Component { edges }
+    }
+
+    pub(super) fn references(&self, edge: &Edge<'a>) -> usize {
+        self.edges.get(edge).copied().unwrap_or_default()
+    }
+
+    // The shortest cycle through each dependency, without duplicates. Each
+    // cycle is a list of packs that depend on the next one, the last
+    // depending on the first.
+    pub(super) fn cycles(&self) -> Vec<Vec<&'a str>> {
+        shortest_cycles(&self.edges.keys().copied().collect())
+    }
+
+    // e.g. `packs/bar -> packs/foo (2) -> packs/bar (1)`, with the number of
+    // references backing each dependency
+    pub(super) fn describe_cycle(&self, cycle: &[&str]) -> String {
+        let mut description = cycle[0].to_owned();
+        for edge in cycle_edges(cycle) {
+            description.push_str(&format!(
+                " -> {} ({})",
+                edge.1,
+                self.references(&edge)
+            ));
+        }
+        description
+    }
+
+    // Dependencies that break every cycle when removed. They're picked by the
+    // fewest references per cycle broken, then any that turn out not to be
+    // needed are put back, so the result is minimal but not always the
+    // cheapest possible.
+    pub(super) fn dependencies_to_remove(&self) -> Vec<Edge<'a>> {
+        let mut remaining: BTreeSet<Edge> =
+            self.edges.keys().copied().collect();
+        let mut removed: Vec<Edge> = vec![];
+        loop {
+            let mut unbroken: Vec<Vec<Edge>> = shortest_cycles(&remaining)
+                .iter()
+                .map(|cycle| cycle_edges(cycle))
+                .collect();
+            if unbroken.is_empty() {
+                break;
+            }
+            while !unbroken.is_empty() {
+                let mut cycles_per_edge: BTreeMap<Edge, usize> =
+                    BTreeMap::new();
+                for edge in unbroken.iter().flatten() {
+                    *cycles_per_edge.entry(*edge).or_default() += 1;
+                }
+                let (edge, _) = cycles_per_edge
+                    .into_iter()
+                    .min_by(|(a, a_cycles), (b, b_cycles)| {
+                        (self.references(a) * b_cycles)
+                            .cmp(&(self.references(b) * a_cycles))
+                            .then(b_cycles.cmp(a_cycles))
+                    })
+                    .expect("Unbroken cycles have edges");
+                remaining.remove(&edge);
+                removed.push(edge);
+                unbroken.retain(|cycle| !cycle.contains(&edge));
+            }
+        }
+
+        // Put back the most referenced dependencies first
+        removed.sort_by_key(|edge| std::cmp::Reverse(self.references(edge)));
+        let mut needed = vec![];
+        for edge in removed {
+            remaining.insert(edge);
+            if !shortest_cycles(&remaining).is_empty() {
+                remaining.remove(&edge);
+                needed.push(edge);
+            }
+        }
+        needed.sort();
+        needed
+    }
+}
+
+fn cycle_edges<'a>(cycle: &[&'a str]) -> Vec<Edge<'a>> {
+    cycle
+        .iter()
+        .zip(cycle.iter().cycle().skip(1))
+        .map(|(from, to)| (*from, *to))
+        .collect()
+}
+
+// The number of references from each pack to constants of each other pack
+pub(super) fn count_references(
+    configuration: &Configuration,
+) -> anyhow::Result<HashMap<(String, String), usize>> {
+    let references =
+        get_all_references(configuration, &configuration.included_files)?;
+    let mut counts = HashMap::new();
+    for reference in references {
+        if let Some(defining_pack_name) = reference.defining_pack_name {
+            *counts
+                .entry((reference.referencing_pack_name, defining_pack_name))
+                .or_default() += 1;
+        }
+    }
+    Ok(counts)
+}
+
+fn shortest_cycles<'a>(edges: &BTreeSet<Edge<'a>>) -> Vec<Vec<&'a str>> {
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, to) in edges {
+        successors.entry(from).or_default().push(to);
+    }
+
+    let mut cycles: BTreeSet<(usize, Vec<&str>)> = BTreeSet::new();
+    for (from, to) in edges {
+        let Some(path) = shortest_path(&successors, to, from) else {
+            continue;
+        };
+        // `from` depends on `to`, which leads back to `from`
+        let mut cycle: Vec<&str> = vec![from];
+        cycle.extend(&path[..path.len() - 1]);
+        let first = cycle
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, pack)| **pack)
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+        cycle.rotate_left(first);
+        cycles.insert((cycle.len(), cycle));
+    }
+    cycles.into_iter().map(|(_, cycle)| cycle).collect()
+}
+
+// Breadth-first, so the path has the fewest dependencies. Includes both ends.
+fn shortest_path<'a>(
+    successors: &HashMap<&'a str, Vec<&'a str>>,
+    from: &'a str,
+    to: &'a str,
+) -> Option<Vec<&'a str>> {
+    let mut predecessors: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(pack) = queue.pop_front() {
+        if pack == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while current != from {
+                current = predecessors[current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in successors.get(pack).into_iter().flatten() {
+            if *next != from && !predecessors.contains_key(next) {
+                predecessors.insert(next, pack);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_component<'a>(
+        edges: &[(&'a str, &'a str, usize)],
+    ) -> Component<'a> {
+        Component::new(
+            edges
+                .iter()
+                .map(|(from, to, references)| ((*from, *to), *references))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_cycles() {
+        let component = build_component(&[
+            ("foo", "bar", 3),
+            ("bar", "foo", 0),
+            ("bar", "baz", 1),
+            ("baz", "foo", 2),
+        ]);
+        assert_eq!(
+            component.cycles(),
+            vec![vec!["bar", "foo"], vec!["bar", "baz", "foo"]]
+        );
+        assert_eq!(
+            cycle_edges(&["bar", "baz", "foo"]),
+            vec![("bar", "baz"), ("baz", "foo"), ("foo", "bar")]
+        );
+    }
+
+    #[test]
+    fn test_describe_cycle() {
+        let component =
+            build_component(&[("foo", "bar", 3), ("bar", "foo", 0)]);
+        assert_eq!(
+            component.describe_cycle(&["bar", "foo"]),
+            "bar -> foo (0) -> bar (3)"
+        );
+    }
+
+    #[test]
+    fn test_dependencies_to_remove() {
+        let component = build_component(&[
+            ("foo", "bar", 3),
+            ("bar", "foo", 0),
+            ("bar", "baz", 1),
+            ("baz", "foo", 2),
+        ]);
+        assert_eq!(
+            component.dependencies_to_remove(),
+            vec![("bar", "baz"), ("bar", "foo")]
+        );
+
+        // One dependency in every cycle is cheaper than one in each
+        let component = build_component(&[
+            ("a", "b", 1),
+            ("b", "a", 1),
+            ("b", "c", 1),
+            ("c", "a", 1),
+        ]);
+        assert_eq!(component.dependencies_to_remove(), vec![("a", "b")]);
+    }
+}
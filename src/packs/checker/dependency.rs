@@ -1,11 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use super::output_helper::print_reference_location;
+use super::cycles::{count_references, Component, Edge};
 use super::pack_checker::PackChecker;
 use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::Reference;
-use crate::packs::pack::Pack;
-use crate::packs::{Configuration, Violation};
+use crate::packs::pack::{is_pack_name_glob, Pack};
+use crate::packs::{Configuration, PackSet, Violation};
 use anyhow::Context;
 use petgraph::algo::tarjan_scc;
 use petgraph::prelude::DiGraph;
@@ -36,7 +36,8 @@ impl ValidatorInterface for Checker {
                 .to_owned();
             graph.add_edge(from_node, to_node, ());
         };
-        let mut error_messages: Vec<String> = vec![];
+        let mut error_messages: Vec<String> = unmatched_globs(configuration);
+        let mut dependencies: Vec<(&str, &str)> = vec![];
 
         match configuration.pack_set.all_pack_dependencies(configuration) {
             Ok(pack_dependencies) => {
@@ -54,6 +55,10 @@ impl ValidatorInterface for Checker {
                             pack_dependency.from_pack,
                             pack_dependency.to_pack,
                         );
+                        dependencies.push((
+                            &pack_dependency.from_pack.name,
+                            &pack_dependency.to_pack.name,
+                        ));
                     }
                 }
             }
@@ -64,19 +69,38 @@ impl ValidatorInterface for Checker {
         }
 
         let mut sccs = vec![];
+        let mut reference_counts = None;
         let strongly_componented_components = tarjan_scc(&graph);
         for component in strongly_componented_components {
             if component.len() > 1 {
-                let pack_names: Vec<String> = component
+                let pack_names: Vec<&str> = component
                     .iter()
                     .map(|node_index| {
                         let pack = node_to_pack
                             .get(node_index)
                             .expect("Could not find pack name for node index");
-                        pack.name.to_owned()
+                        pack.name.as_str()
                     })
                     .collect();
-                sccs.push(pack_names.join(", "));
+                // Only worth parsing the project for once there's a cycle
+                let reference_counts =
+                    reference_counts.get_or_insert_with(|| {
+                        count_references(configuration).unwrap_or_else(
+                            |error| {
+                                error_messages.push(format!(
+                                    "Failed to count references between packs: {:#}",
+                                    error
+                                ));
+                                HashMap::new()
+                            },
+                        )
+                    });
+                sccs.push(describe_component(
+                    &configuration.pack_set,
+                    &pack_names,
+                    &dependencies,
+                    reference_counts,
+                ));
             }
         }
 
@@ -103,6 +127,96 @@ The following groups of packages form a cycle:
     }
 }
 
+// Globs in `dependencies`, `dev_dependencies`, `ignored_dependencies` or
+// `visible_to` that match no other pack, e.g. because of a typo
+fn unmatched_globs(configuration: &Configuration) -> Vec<String> {
+    let mut error_messages = vec![];
+    for pack in &configuration.pack_set.packs {
+        let keys = [
+            ("dependencies", Some(&pack.dependencies)),
+            ("dev_dependencies", Some(&pack.dev_dependencies)),
+            ("ignored_dependencies", Some(&pack.ignored_dependencies)),
+            ("visible_to", pack.visible_to.as_ref()),
+        ];
+        for (key, entries) in keys {
+            let mut globs: Vec<&String> = entries
+                .into_iter()
+                .flatten()
+                .filter(|entry| is_pack_name_glob(entry))
+                .collect();
+            globs.sort();
+            for glob in globs {
+                let matches_other_pack = configuration
+                    .pack_set
+                    .matching_packs(glob)
+                    .iter()
+                    .any(|other_pack| other_pack.name != pack.name);
+                if !matches_other_pack {
+                    error_messages.push(format!(
+                        "{} has '{}' in its {}, but that glob matches no other pack. Try `packs list-packs` to debug.",
+                        pack.relative_yml().to_string_lossy(),
+                        glob,
+                        key
+                    ));
+                }
+            }
+        }
+    }
+    error_messages
+}
+
+// The glob in `dependencies` a dependency comes from, if it isn't listed by
+// name
+fn declaring_glob<'a>(pack_set: &'a PackSet, edge: Edge) -> Option<&'a str> {
+    let (from, to) = edge;
+    pack_set.for_pack(from).ok()?.dependency_glob(to)
+}
+
+fn describe_component(
+    pack_set: &PackSet,
+    pack_names: &[&str],
+    dependencies: &[(&str, &str)],
+    reference_counts: &HashMap<(String, String), usize>,
+) -> String {
+    let packs: HashSet<&str> = pack_names.iter().copied().collect();
+    let edges: BTreeMap<(&str, &str), usize> = dependencies
+        .iter()
+        .filter(|(from, to)| packs.contains(from) && packs.contains(to))
+        .map(|&(from, to)| {
+            let count = reference_counts
+                .get(&(from.to_owned(), to.to_owned()))
+                .copied()
+                .unwrap_or_default();
+            ((from, to), count)
+        })
+        .collect();
+    let component = Component::new(edges);
+
+    let mut description = format!(
+        "{}\nCycles, with the number of references backing each dependency:",
+        pack_names.join(", ")
+    );
+    for cycle in component.cycles() {
+        description.push_str("\n- ");
+        description.push_str(&component.describe_cycle(&cycle));
+    }
+    description.push_str("\nRemoving these dependencies breaks every cycle:");
+    for edge in component.dependencies_to_remove() {
+        let references = component.references(&edge);
+        description.push_str(&format!(
+            "\n- {} -> {} ({} reference{}{})",
+            edge.0,
+            edge.1,
+            references,
+            if references == 1 { "" } else { "s" },
+            declaring_glob(pack_set, edge)
+                .map(|glob| format!(", declared by `{}`", glob))
+                .unwrap_or_default()
+        ));
+    }
+    description
+}
+
 // TODO: Add test for does not enforce dependencies
 impl CheckerInterface for Checker {
     fn check(
@@ -117,16 +231,19 @@ impl CheckerInterface for Checker {
         }
         let defining_pack = pack_checker.defining_pack.unwrap();
 
-        let referencing_pack_dependencies =
-            &pack_checker.referencing_pack.dependencies;
-
-        let ignored_dependency = pack_checker
-            .referencing_pack
-            .ignored_dependencies
-            .contains(&defining_pack.name);
-
-        if referencing_pack_dependencies.contains(&defining_pack.name)
-            || ignored_dependency
+        let referencing_pack = pack_checker.referencing_pack;
+        if referencing_pack.depends_on(&defining_pack.name)
+            || referencing_pack.ignores_dependency_on(&defining_pack.name)
+        {
+            return Ok(None);
+        }
+        let is_dev_dependency =
+            referencing_pack.dev_depends_on(&defining_pack.name);
+        if is_dev_dependency
+            && configuration.is_test_file(
+                referencing_pack,
+                &reference.relative_referencing_file,
+            )
         {
             return Ok(None);
         }
@@ -154,20 +271,25 @@ impl CheckerInterface for Checker {
         // To receive help interpreting or resolving this error message, see: https://github.com/Shopify/packwerk/blob/main/TROUBLESHOOT.md#Troubleshooting-violations
         // END: Original packwerk message
 
-        let loc = print_reference_location(reference);
-        let message = format!(
-                "{}Dependency violation: `{}` belongs to `{}`, but `{}` does not specify a dependency on `{}`.",
-                loc,
+        let message = if is_dev_dependency {
+            format!(
+                "Dependency violation: `{}` belongs to `{}`, but `{}` only specifies a dev dependency on `{}`, which only test files may use.",
                 reference.constant_name,
                 defining_pack.name,
-                pack_checker.referencing_pack.relative_yml().to_string_lossy(),
+                referencing_pack.relative_yml().to_string_lossy(),
                 defining_pack.name,
-            );
+            )
+        } else {
+            format!(
+                "Dependency violation: `{}` belongs to `{}`, but `{}` does not specify a dependency on `{}`.",
+                reference.constant_name,
+                defining_pack.name,
+                referencing_pack.relative_yml().to_string_lossy(),
+                defining_pack.name,
+            )
+        };
 
-        Ok(Some(Violation {
-            message,
-            identifier: pack_checker.violation_identifier(),
-        }))
+        Ok(Some(pack_checker.violation(message)))
     }
 
     fn violation_type(&self) -> String {
@@ -226,7 +348,7 @@ mod tests {
                 enforce_dependencies: Some(CheckerSetting::True),
                 ..default_referencing_pack()},
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nDependency violation: `::Bar` belongs to `packs/bar`, but `packs/foo/package.yml` does not specify a dependency on `packs/bar`.".to_string(),
+                "Dependency violation: `::Bar` belongs to `packs/bar`, but `packs/foo/package.yml` does not specify a dependency on `packs/bar`.".to_string(),
                 "dependency".to_string(), false)),
         };
         test_check(&Checker {}, &mut test_checker)
@@ -247,7 +369,7 @@ mod tests {
                 enforce_dependencies: Some(CheckerSetting::Strict),
                 ..default_referencing_pack()},
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nDependency violation: `::Bar` belongs to `packs/bar`, but `packs/foo/package.yml` does not specify a dependency on `packs/bar`.".to_string(),
+                "Dependency violation: `::Bar` belongs to `packs/bar`, but `packs/foo/package.yml` does not specify a dependency on `packs/bar`.".to_string(),
                 "dependency".to_string(), true)),
         };
         test_check(&Checker {}, &mut test_checker)
@@ -326,7 +448,11 @@ mod tests {
 Found 1 strongly connected components (i.e. dependency cycles)
 The following groups of packages form a cycle:
 
-packs/foo, packs/bar",
+packs/foo, packs/bar
+Cycles, with the number of references backing each dependency:
+- packs/bar -> packs/foo (0) -> packs/bar (0)
+Removing these dependencies breaks every cycle:
+- packs/bar -> packs/foo (0 references)",
         )];
         assert_eq!(error, Some(expected_message));
     }
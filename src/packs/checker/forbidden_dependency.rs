@@ -0,0 +1,361 @@
+use anyhow::Context;
+use globset::{GlobBuilder, GlobMatcher};
+
+use super::pack_checker::PackChecker;
+use super::{CheckerInterface, ValidatorInterface};
+use crate::packs::checker::Reference;
+use crate::packs::pack::{CheckerSetting, Pack};
+use crate::packs::raw_configuration::RawForbiddenDependency;
+use crate::packs::{Configuration, Violation};
+
+// A `forbidden_dependencies` entry in `packwerk.yml`
+#[derive(Debug, Clone)]
+pub struct ForbiddenDependency {
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+    pub setting: CheckerSetting,
+    from_matcher: GlobMatcher,
+    to_matcher: GlobMatcher,
+}
+
+impl ForbiddenDependency {
+    pub(crate) fn new(raw: RawForbiddenDependency) -> anyhow::Result<Self> {
+        Ok(ForbiddenDependency {
+            from_matcher: pack_name_matcher(&raw.from)?,
+            to_matcher: pack_name_matcher(&raw.to)?,
+            from: raw.from,
+            to: raw.to,
+            reason: raw.reason,
+            setting: raw.enforce.unwrap_or(CheckerSetting::True),
+        })
+    }
+
+    fn matches(&self, from_pack_name: &str, to_pack_name: &str) -> bool {
+        !self.setting.is_false()
+            && matches_pack_name(&self.from_matcher, &self.from, from_pack_name)
+            && matches_pack_name(&self.to_matcher, &self.to, to_pack_name)
+    }
+
+    fn describe(&self) -> String {
+        let rule = format!("`{}` -> `{}`", self.from, self.to);
+        if self.reason.is_empty() {
+            rule
+        } else {
+            format!("{} ({})", rule, self.reason)
+        }
+    }
+}
+
+fn pack_name_matcher(glob: &str) -> anyhow::Result<GlobMatcher> {
+    Ok(GlobBuilder::new(glob)
+        .literal_separator(true)
+        .build()
+        .context(format!(
+            "Invalid glob `{}` in `forbidden_dependencies` in `packwerk.yml`",
+            glob
+        ))?
+        .compile_matcher())
+}
+
+// `packs/payments/**` also matches `packs/payments` itself
+fn matches_pack_name(matcher: &GlobMatcher, glob: &str, name: &str) -> bool {
+    matcher.is_match(name) || glob.strip_suffix("/**") == Some(name)
+}
+
+// The rule forbidding one pack from depending on another, preferring strict
+// rules when several match
+pub(crate) fn forbidding_rule<'a>(
+    configuration: &'a Configuration,
+    from_pack_name: &str,
+    to_pack_name: &str,
+) -> Option<&'a ForbiddenDependency> {
+    configuration
+        .forbidden_dependencies
+        .iter()
+        .filter(|rule| rule.matches(from_pack_name, to_pack_name))
+        .max_by_key(|rule| rule.setting.is_strict())
+}
+
+pub struct Checker {}
+
+impl CheckerInterface for Checker {
+    fn check(
+        &self,
+        reference: &Reference,
+        configuration: &Configuration,
+    ) -> anyhow::Result<Option<Violation>> {
+        let pack_checker =
+            PackChecker::new(configuration, reference, &self.violation_type())?;
+        if !pack_checker.checkable()? {
+            return Ok(None);
+        }
+        let defining_pack = pack_checker.defining_pack.unwrap();
+        let Some(rule) = forbidding_rule(
+            configuration,
+            &pack_checker.referencing_pack.name,
+            &defining_pack.name,
+        ) else {
+            return Ok(None);
+        };
+
+        let message = format!(
+            "Forbidden dependency violation: `{}` belongs to `{}`, which `{}` must never depend on, see {} in `packwerk.yml`",
+            reference.constant_name,
+            defining_pack.name,
+            pack_checker.referencing_pack.name,
+            rule.describe(),
+        );
+
+        Ok(Some(pack_checker.violation(message)))
+    }
+
+    fn violation_type(&self) -> String {
+        "forbidden_dependency".to_owned()
+    }
+}
+
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let mut error_messages: Vec<String> = vec![];
+        for pack in &configuration.pack_set.packs {
+            let keys = [
+                ("dependencies", &pack.dependencies),
+                ("dev_dependencies", &pack.dev_dependencies),
+            ];
+            for (key, entries) in keys {
+                let mut dependencies: Vec<&String> = entries.iter().collect();
+                dependencies.sort();
+                for dependency in dependencies {
+                    error_messages.extend(forbidden_dependency_errors(
+                        configuration,
+                        pack,
+                        key,
+                        dependency,
+                    ));
+                }
+            }
+        }
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+}
+
+// Globs forbid every pack they match
+fn forbidden_dependency_errors(
+    configuration: &Configuration,
+    pack: &Pack,
+    key: &str,
+    dependency: &str,
+) -> Vec<String> {
+    let mut to_packs = configuration.pack_set.matching_packs(dependency);
+    to_packs.sort_by(|a, b| a.name.cmp(&b.name));
+    to_packs
+        .into_iter()
+        .filter(|to_pack| to_pack.name != pack.name)
+        .filter_map(|to_pack| {
+            let rule = forbidding_rule(configuration, &pack.name, &to_pack.name)?;
+            let declared_by = if to_pack.name == dependency {
+                String::new()
+            } else {
+                format!(" (declared by `{}`)", dependency)
+            };
+            Some(format!(
+                "Invalid '{}' in '{}'. Depending on `{}`{} is forbidden by {} in `packwerk.yml`",
+                key,
+                pack.relative_yml().to_string_lossy(),
+                to_pack.name,
+                declared_by,
+                rule.describe(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::path::PathBuf;
+
+    use crate::packs::checker::common_test::tests::{
+        build_expected_violation, default_defining_pack,
+        default_referencing_pack, test_check, TestChecker,
+    };
+    use crate::packs::pack::Pack;
+    use crate::packs::PackSet;
+
+    use super::*;
+
+    fn rule(
+        from: &str,
+        to: &str,
+        enforce: CheckerSetting,
+    ) -> ForbiddenDependency {
+        ForbiddenDependency::new(RawForbiddenDependency {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            reason: String::from("keep them apart"),
+            enforce: Some(enforce),
+        })
+        .unwrap()
+    }
+
+    fn configuration_with_rules(
+        packs: Vec<Pack>,
+        forbidden_dependencies: Vec<ForbiddenDependency>,
+    ) -> Configuration {
+        let root_pack = Pack {
+            name: String::from("."),
+            ..Pack::default()
+        };
+        let mut packs = packs;
+        packs.push(root_pack);
+        Configuration {
+            pack_set: PackSet::build(HashSet::from_iter(packs), HashMap::new())
+                .unwrap(),
+            forbidden_dependencies,
+            ..Configuration::default()
+        }
+    }
+
+    fn test_checker(
+        forbidden_dependencies: Vec<ForbiddenDependency>,
+        expected_violation: Option<Violation>,
+    ) -> TestChecker {
+        TestChecker {
+            configuration: Some(configuration_with_rules(
+                vec![default_referencing_pack(), default_defining_pack()],
+                forbidden_dependencies,
+            )),
+            referenced_constant_name: Some(String::from("::Bar")),
+            expected_violation,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matches_pack_names() {
+        let rule = rule("packs/payments/**", "packs/*", CheckerSetting::True);
+        assert!(rule.matches("packs/payments", "packs/marketing"));
+        assert!(rule.matches("packs/payments/api", "packs/marketing"));
+        assert!(!rule.matches("packs/payments_v2", "packs/marketing"));
+        assert!(!rule.matches("packs/payments", "packs/marketing/emails"));
+    }
+
+    #[test]
+    fn test_with_violation() -> anyhow::Result<()> {
+        test_check(
+            &Checker {},
+            &mut test_checker(
+                vec![rule("packs/foo", "packs/bar", CheckerSetting::True)],
+                Some(build_expected_violation(
+                    "Forbidden dependency violation: `::Bar` belongs to `packs/bar`, which `packs/foo` must never depend on, see `packs/foo` -> `packs/bar` (keep them apart) in `packwerk.yml`".to_string(),
+                    "forbidden_dependency".to_string(),
+                    false,
+                )),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_with_strict_violation() -> anyhow::Result<()> {
+        test_check(
+            &Checker {},
+            &mut test_checker(
+                vec![
+                    rule("packs/*", "packs/bar", CheckerSetting::True),
+                    rule("packs/foo", "packs/*", CheckerSetting::Strict),
+                ],
+                Some(build_expected_violation(
+                    "Forbidden dependency violation: `::Bar` belongs to `packs/bar`, which `packs/foo` must never depend on, see `packs/foo` -> `packs/*` (keep them apart) in `packwerk.yml`".to_string(),
+                    "forbidden_dependency".to_string(),
+                    true,
+                )),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_without_matching_rule() -> anyhow::Result<()> {
+        test_check(
+            &Checker {},
+            &mut test_checker(
+                vec![
+                    rule("packs/bar", "packs/foo", CheckerSetting::True),
+                    rule("packs/foo", "packs/bar", CheckerSetting::False),
+                ],
+                None,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let configuration = configuration_with_rules(
+            vec![
+                Pack {
+                    name: String::from("packs/foo"),
+                    relative_path: PathBuf::from("packs/foo"),
+                    dependencies: HashSet::from([
+                        String::from("packs/bar"),
+                        String::from("packs/baz"),
+                    ]),
+                    dev_dependencies: HashSet::from([String::from(
+                        "packs/qux",
+                    )]),
+                    ..Pack::default()
+                },
+                default_defining_pack(),
+                Pack {
+                    name: String::from("packs/qux"),
+                    ..Pack::default()
+                },
+            ],
+            vec![
+                rule("packs/foo", "packs/bar", CheckerSetting::Strict),
+                rule("packs/foo", "packs/qux", CheckerSetting::True),
+            ],
+        );
+        assert_eq!(
+            Checker {}.validate(&configuration),
+            Some(vec![
+                String::from(
+                    "Invalid 'dependencies' in 'packs/foo/package.yml'. Depending on `packs/bar` is forbidden by `packs/foo` -> `packs/bar` (keep them apart) in `packwerk.yml`"
+                ),
+                String::from(
+                    "Invalid 'dev_dependencies' in 'packs/foo/package.yml'. Depending on `packs/qux` is forbidden by `packs/foo` -> `packs/qux` (keep them apart) in `packwerk.yml`"
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_glob_dependencies() {
+        let configuration = configuration_with_rules(
+            vec![
+                Pack {
+                    name: String::from("packs/foo"),
+                    relative_path: PathBuf::from("packs/foo"),
+                    dependencies: HashSet::from([String::from("packs/*")]),
+                    ..Pack::default()
+                },
+                default_defining_pack(),
+                Pack {
+                    name: String::from("packs/baz"),
+                    ..Pack::default()
+                },
+            ],
+            vec![rule("packs/foo", "packs/bar", CheckerSetting::True)],
+        );
+        assert_eq!(
+            Checker {}.validate(&configuration),
+            Some(vec![String::from(
+                "Invalid 'dependencies' in 'packs/foo/package.yml'. Depending on `packs/bar` (declared by `packs/*`) is forbidden by `packs/foo` -> `packs/bar` (keep them apart) in `packwerk.yml`"
+            )])
+        );
+    }
+}
@@ -1,19 +1,51 @@
-use super::output_helper::print_reference_location;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::cycles::{Component, Edge};
 use super::pack_checker::PackChecker;
 use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::Reference;
 use crate::packs::pack::{CheckerSetting, Pack};
+use crate::packs::raw_configuration::RawLayers;
 use crate::packs::{Configuration, Violation};
 use anyhow::{bail, Result};
+use petgraph::algo::tarjan_scc;
+use petgraph::prelude::{DiGraph, NodeIndex};
 
 #[derive(Default, Debug, Clone)]
 pub struct Layers {
     pub layers: Vec<String>,
+    // When `layers` in `packwerk.yml` is a map, the layers each layer may
+    // reference besides itself. Otherwise each layer may reference the
+    // layers after it.
+    pub allowed_layers: Option<BTreeMap<String, Vec<String>>>,
 }
 
 const VIOLATION_TYPE: &str = "layer";
 const VIOLATION_NAME: &str = "Layer";
 
+impl From<RawLayers> for Layers {
+    fn from(raw_layers: RawLayers) -> Self {
+        match raw_layers {
+            RawLayers::Ordered(layers) => Layers {
+                layers,
+                allowed_layers: None,
+            },
+            RawLayers::Graph(graph) => {
+                let allowed_layers: BTreeMap<String, Vec<String>> = graph
+                    .into_iter()
+                    .map(|(layer, allowed)| {
+                        (layer, allowed.unwrap_or_default())
+                    })
+                    .collect();
+                Layers {
+                    layers: allowed_layers.keys().cloned().collect(),
+                    allowed_layers: Some(allowed_layers),
+                }
+            }
+        }
+    }
+}
+
 impl Layers {
     fn can_depend_on(
         &self,
@@ -30,7 +62,13 @@ impl Layers {
 
         match (referencing_layer_index, defining_layer_index) {
             (Some(referencing_layer_index), Some(defining_layer_index)) => {
-                Ok(referencing_layer_index <= defining_layer_index)
+                match &self.allowed_layers {
+                    Some(allowed_layers) => Ok(referencing_layer
+                        == defining_layer
+                        || allowed_layers[referencing_layer]
+                            .contains(defining_layer)),
+                    None => Ok(referencing_layer_index <= defining_layer_index),
+                }
             }
             _ => {
                 bail!("Could not find one of layer `{}` or layer `{}` in `packwerk.yml`",
@@ -39,6 +77,59 @@ impl Layers {
         }
     }
 
+    // Problems with the layer graph itself: references to undefined layers
+    // and cycles, which would make layering meaningless
+    fn validate(&self) -> Vec<String> {
+        let Some(allowed_layers) = &self.allowed_layers else {
+            return vec![];
+        };
+        let mut error_messages = vec![];
+        let mut edges: BTreeMap<Edge, usize> = BTreeMap::new();
+        for (layer, allowed) in allowed_layers {
+            for allowed_layer in allowed {
+                if !allowed_layers.contains_key(allowed_layer) {
+                    error_messages.push(format!(
+                        "Layer `{}` may reference layer `{}`, which is not defined in `packwerk.yml`",
+                        layer, allowed_layer
+                    ));
+                } else if layer != allowed_layer {
+                    edges.insert((layer, allowed_layer), 0);
+                }
+            }
+        }
+
+        let mut graph = DiGraph::<&str, ()>::new();
+        let nodes: HashMap<&str, NodeIndex> = allowed_layers
+            .keys()
+            .map(|layer| (layer.as_str(), graph.add_node(layer)))
+            .collect();
+        for (from, to) in edges.keys() {
+            graph.add_edge(nodes[from], nodes[to], ());
+        }
+        for component in tarjan_scc(&graph) {
+            if component.len() < 2 {
+                continue;
+            }
+            let layers: HashSet<&str> =
+                component.iter().map(|node| graph[*node]).collect();
+            let component_edges = edges
+                .iter()
+                .filter(|((from, to), _)| {
+                    layers.contains(from) && layers.contains(to)
+                })
+                .map(|(edge, count)| (*edge, *count))
+                .collect();
+            for cycle in Component::new(component_edges).cycles() {
+                error_messages.push(format!(
+                    "Layers in `packwerk.yml` must not reference each other in a cycle: {} -> {}",
+                    cycle.join(" -> "),
+                    cycle[0]
+                ));
+            }
+        }
+        error_messages
+    }
+
     fn pack_enforces_layers<'a>(&self, pack: &'a Pack) -> &'a CheckerSetting {
         match &pack.enforce_layers {
             Some(setting) => setting,
@@ -83,7 +174,7 @@ impl Checker {
 
 impl ValidatorInterface for Checker {
     fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
-        let mut error_messages: Vec<String> = vec![];
+        let mut error_messages: Vec<String> = self.layers.validate();
 
         for pack in &configuration.pack_set.packs {
             if let Some(error_message) = self.validate_pack(pack) {
@@ -125,11 +216,8 @@ impl CheckerInterface for Checker {
                     return Ok(None);
                 }
 
-                let loc = print_reference_location(reference);
-
                 let message = format!(
-                    "{}{} violation: `{}` belongs to `{}` (whose layer is `{}`) cannot be accessed from `{}` (whose layer is `{}`)",
-                    loc,
+                    "{} violation: `{}` belongs to `{}` (whose layer is `{}`) cannot be accessed from `{}` (whose layer is `{}`)",
                     self.layers.violation_name(),
                     reference.constant_name,
                     defining_pack.name,
@@ -138,10 +226,7 @@ impl CheckerInterface for Checker {
                     referencing_layer,
                 );
 
-                Ok(Some(Violation {
-                    message,
-                    identifier: pack_checker.violation_identifier(),
-                }))
+                Ok(Some(pack_checker.violation(message)))
             }
             _ => Ok(None),
         }
@@ -171,6 +256,18 @@ mod tests {
 
     use super::*;
 
+    // ui -> domain -> persistence, where ui may not skip domain
+    fn checker_with_layer_graph() -> Checker {
+        Checker {
+            layers: Layers::from(
+                serde_yaml::from_str::<RawLayers>(
+                    "ui: [domain]\ndomain: [persistence]\npersistence:",
+                )
+                .unwrap(),
+            ),
+        }
+    }
+
     fn checker_with_layers() -> Checker {
         Checker {
             layers: Layers {
@@ -178,6 +275,7 @@ mod tests {
                     String::from("product"),
                     String::from("utilities"),
                 ],
+                allowed_layers: None,
             },
         }
     }
@@ -219,7 +317,7 @@ mod tests {
                 ..default_referencing_pack()
             },
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nLayer violation: `::Bar` belongs to `packs/bar` (whose layer is `product`) cannot be accessed from `packs/foo` (whose layer is `utilities`)".to_string(), 
+                "Layer violation: `::Bar` belongs to `packs/bar` (whose layer is `product`) cannot be accessed from `packs/foo` (whose layer is `utilities`)".to_string(), 
                 "layer".to_string(), false)),
         };
         test_check(&checker_with_layers(), &mut test_checker)
@@ -243,7 +341,7 @@ mod tests {
                 ..default_referencing_pack()
             },
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nLayer violation: `::Bar` belongs to `packs/bar` (whose layer is `product`) cannot be accessed from `packs/foo` (whose layer is `utilities`)".to_string(), 
+                "Layer violation: `::Bar` belongs to `packs/bar` (whose layer is `product`) cannot be accessed from `packs/foo` (whose layer is `utilities`)".to_string(), 
                 "layer".to_string(), true)),
         };
         test_check(&checker_with_layers(), &mut test_checker)
@@ -356,6 +454,7 @@ mod tests {
         let checker = Checker {
             layers: Layers {
                 layers: config_layers,
+                allowed_layers: None,
             },
         };
         checker.validate(&configuration)
@@ -477,6 +576,7 @@ mod tests {
                     String::from("product"),
                     String::from("utilities"),
                 ],
+                allowed_layers: None,
             },
         };
 
@@ -492,4 +592,66 @@ mod tests {
         ];
         assert_eq!(errors, expected_errors);
     }
+
+    #[test]
+    fn test_can_depend_on_with_layer_graph() -> anyhow::Result<()> {
+        let layers = checker_with_layer_graph().layers;
+        let can_depend_on = |referencing: &str, defining: &str| {
+            layers.can_depend_on(&referencing.to_owned(), &defining.to_owned())
+        };
+        assert!(can_depend_on("ui", "ui")?);
+        assert!(can_depend_on("ui", "domain")?);
+        assert!(can_depend_on("domain", "persistence")?);
+        assert!(!can_depend_on("ui", "persistence")?);
+        assert!(!can_depend_on("persistence", "domain")?);
+        assert!(can_depend_on("ui", "unknown").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn reference_skipping_a_layer_is_a_layer_violation() -> anyhow::Result<()> {
+        let mut test_checker = TestChecker {
+            reference: None,
+            configuration: None,
+            referenced_constant_name: Some(String::from("::Bar")),
+            defining_pack: Some(Pack {
+                name: "packs/bar".to_owned(),
+                layer: Some("persistence".to_string()),
+                ..default_defining_pack()
+            }),
+            referencing_pack: Pack {
+                name: "packs/foo".to_owned(),
+                enforce_layers: Some(CheckerSetting::True),
+                layer: Some("ui".to_string()),
+                ..default_referencing_pack()
+            },
+            expected_violation: Some(build_expected_violation(
+                "Layer violation: `::Bar` belongs to `packs/bar` (whose layer is `persistence`) cannot be accessed from `packs/foo` (whose layer is `ui`)".to_string(),
+                "layer".to_string(), false)),
+        };
+        test_check(&checker_with_layer_graph(), &mut test_checker)
+    }
+
+    #[test]
+    fn test_validate_layer_graph() {
+        let configuration = Configuration::default();
+        assert_eq!(checker_with_layer_graph().validate(&configuration), None);
+
+        let checker = Checker {
+            layers: Layers::from(
+                serde_yaml::from_str::<RawLayers>(
+                    "ui: [domain, api]\ndomain: [persistence, ui]\npersistence: [domain]",
+                )
+                .unwrap(),
+            ),
+        };
+        assert_eq!(
+            checker.validate(&configuration),
+            Some(vec![
+                "Layer `ui` may reference layer `api`, which is not defined in `packwerk.yml`".to_string(),
+                "Layers in `packwerk.yml` must not reference each other in a cycle: domain -> persistence -> domain".to_string(),
+                "Layers in `packwerk.yml` must not reference each other in a cycle: domain -> ui -> domain".to_string(),
+            ])
+        );
+    }
 }
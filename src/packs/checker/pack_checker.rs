@@ -1,9 +1,12 @@
 use crate::packs::{
     pack::{CheckerSetting, Pack},
-    Configuration,
+    Configuration, Violation,
 };
 
-use super::{reference::Reference, ViolationIdentifier};
+use super::{
+    forbidden_dependency::forbidding_rule, reference::Reference,
+    ViolationIdentifier,
+};
 
 pub struct PackChecker<'a> {
     pub configuration: &'a Configuration,
@@ -22,6 +25,7 @@ enum ViolationDirection {
 pub enum ViolationType {
     Dependency,
     FolderPrivacy,
+    ForbiddenDependency,
     Layer,
     Privacy,
     Visibility,
@@ -32,6 +36,7 @@ impl From<&str> for ViolationType {
         match s {
             "dependency" => ViolationType::Dependency,
             "folder_privacy" => ViolationType::FolderPrivacy,
+            "forbidden_dependency" => ViolationType::ForbiddenDependency,
             "layer" => ViolationType::Layer,
             "privacy" => ViolationType::Privacy,
             "visibility" => ViolationType::Visibility,
@@ -45,6 +50,7 @@ impl From<ViolationType> for &str {
         match violation_type {
             ViolationType::Dependency => "dependency",
             ViolationType::FolderPrivacy => "folder_privacy",
+            ViolationType::ForbiddenDependency => "forbidden_dependency",
             ViolationType::Layer => "layer",
             ViolationType::Privacy => "privacy",
             ViolationType::Visibility => "visibility",
@@ -70,9 +76,9 @@ impl<'a> PackChecker<'a> {
 
     fn violation_direction(&self) -> ViolationDirection {
         match self.violation_type {
-            ViolationType::Dependency | ViolationType::Layer => {
-                ViolationDirection::Outgoing
-            }
+            ViolationType::Dependency
+            | ViolationType::ForbiddenDependency
+            | ViolationType::Layer => ViolationDirection::Outgoing,
             ViolationType::Privacy
             | ViolationType::FolderPrivacy
             | ViolationType::Visibility => ViolationDirection::Incoming,
@@ -117,6 +123,13 @@ impl<'a> PackChecker<'a> {
             ViolationType::FolderPrivacy => {
                 self.rules_pack().enforce_folder_privacy()
             }
+            // Set by the rule in `packwerk.yml`, not by the pack
+            ViolationType::ForbiddenDependency => forbidding_rule(
+                self.configuration,
+                self.referencing_pack_name(),
+                self.defining_pack_name(),
+            )
+            .map_or(&CheckerSetting::False, |rule| &rule.setting),
             ViolationType::Layer => {
                 self.checker_setting_for(&self.rules_pack().enforce_layers)
             }
@@ -137,6 +150,7 @@ impl<'a> PackChecker<'a> {
             ViolationType::FolderPrivacy => {
                 self.configuration.disable_enforce_folder_privacy
             }
+            ViolationType::ForbiddenDependency => false,
             ViolationType::Layer => self.configuration.disable_enforce_layers,
             ViolationType::Privacy => {
                 self.configuration.disable_enforce_privacy
@@ -190,4 +204,16 @@ impl<'a> PackChecker<'a> {
             defining_pack_name: self.defining_pack.unwrap().name.clone(),
         }
     }
+
+    pub fn violation(&self, message: String) -> Violation {
+        Violation {
+            message,
+            identifier: self.violation_identifier(),
+            source_location: self.reference.source_location.clone(),
+            relative_defining_file: self
+                .reference
+                .relative_defining_file
+                .clone(),
+        }
+    }
 }
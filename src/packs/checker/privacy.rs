@@ -1,7 +1,13 @@
-use super::output_helper::print_reference_location;
+use std::collections::HashMap;
+
 use super::pack_checker::PackChecker;
-use super::CheckerInterface;
+use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::Reference;
+use crate::packs::constant_resolver::ConstantDefinition;
+use crate::packs::pack::{
+    is_valid_public_constant, public_constant_matches, Pack,
+};
+use crate::packs::reference_extractor::get_constant_resolver;
 use crate::packs::{Configuration, Violation};
 
 pub struct Checker {}
@@ -25,22 +31,17 @@ impl CheckerInterface for Checker {
             return Ok(None);
         }
 
-        // This is a hack for now – we need to read package.yml file public_paths at some point,
-        // and probably find a better way to check if the constant is public
-
-        let public_folder = &defining_pack.public_folder();
-        let is_public = reference
-            .relative_defining_file
-            .as_ref()
-            .unwrap()
-            .starts_with(public_folder.to_string_lossy().as_ref());
+        let is_public = defining_pack
+            .is_public_file(reference.relative_defining_file.as_ref().unwrap());
 
         // Note this means that if the constant is ALSO in the list of private_constants,
         // it will be considered public.
         // This is how packwerk does it today.
         // Later we might want to add some sort of validation that a constant can be in the public folder OR in the list of private_constants,
         // but not both.
-        if is_public {
+        if is_public
+            || defining_pack.declares_public_constant(&reference.constant_name)
+        {
             return Ok(None);
         }
 
@@ -70,20 +71,15 @@ impl CheckerInterface for Checker {
         // Inference details: this is a reference to ::Constant which seems to be defined in packs/defining_pack/path/to/definition.rb.
         // To receive help interpreting or resolving this error message, see: https://github.com/Shopify/packwerk/blob/main/TROUBLESHOOT.md#Troubleshooting-violations
         // END: Original packwerk message
-        let loc = print_reference_location(reference);
 
         let message = format!(
-            "{}Privacy violation: `{}` is private to `{}`, but referenced from `{}`",
-            loc,
+            "Privacy violation: `{}` is private to `{}`, but referenced from `{}`",
             reference.constant_name,
             defining_pack.name,
             &pack_checker.referencing_pack.name,
         );
 
-        Ok(Some(Violation {
-            message,
-            identifier: pack_checker.violation_identifier(),
-        }))
+        Ok(Some(pack_checker.violation(message)))
     }
 
     fn violation_type(&self) -> String {
@@ -91,6 +87,108 @@ impl CheckerInterface for Checker {
     }
 }
 
+// `public_constants` entries are either a constant name or a namespace
+// ending in `::*`. Entries that match no constant defined in their pack, e.g.
+// because the constant was renamed or moved to another pack, are warnings.
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let mut error_messages: Vec<String> = vec![];
+        for pack in packs_with_public_constants(configuration) {
+            for public_constant in sorted_public_constants(pack) {
+                if !is_valid_public_constant(public_constant) {
+                    error_messages.push(format!(
+                        "Invalid 'public_constants' in '{}'. `{}` must be a constant name, or a namespace ending in `::*`",
+                        pack.relative_yml().to_string_lossy(),
+                        public_constant,
+                    ));
+                }
+            }
+        }
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+
+    fn warn(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let packs = packs_with_public_constants(configuration);
+        if packs.is_empty() {
+            return None;
+        }
+
+        // Resolving constants means parsing the project, which `validate`
+        // otherwise doesn't need
+        let constant_resolver = match get_constant_resolver(configuration) {
+            Ok(constant_resolver) => constant_resolver,
+            Err(error) => {
+                return Some(vec![format!(
+                    "Failed to resolve `public_constants`: {:#}",
+                    error
+                )])
+            }
+        };
+        let definitions: &HashMap<String, Vec<ConstantDefinition>> =
+            constant_resolver
+                .fully_qualified_constant_name_to_constant_definition_map();
+
+        let mut warnings: Vec<String> = vec![];
+        for pack in packs {
+            for public_constant in sorted_public_constants(pack) {
+                if !is_valid_public_constant(public_constant) {
+                    continue;
+                }
+                let defined_in_pack = definitions.iter().any(|(name, defs)| {
+                    public_constant_matches(public_constant, name)
+                        && defs.iter().any(|definition| {
+                            configuration
+                                .pack_set
+                                .for_file(
+                                    &definition.absolute_path_of_definition,
+                                )
+                                .ok()
+                                .flatten()
+                                .is_some_and(|owner| owner.name == pack.name)
+                        })
+                });
+                if !defined_in_pack {
+                    warnings.push(format!(
+                        "'public_constants' in '{}': `{}` does not match any constant defined in `{}`",
+                        pack.relative_yml().to_string_lossy(),
+                        public_constant,
+                        pack.name,
+                    ));
+                }
+            }
+        }
+
+        if warnings.is_empty() {
+            None
+        } else {
+            Some(warnings)
+        }
+    }
+}
+
+fn packs_with_public_constants(configuration: &Configuration) -> Vec<&Pack> {
+    let mut packs: Vec<&Pack> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .filter(|pack| !pack.public_constants.is_empty())
+        .collect();
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    packs
+}
+
+fn sorted_public_constants(pack: &Pack) -> Vec<&String> {
+    let mut public_constants: Vec<&String> =
+        pack.public_constants.iter().collect();
+    public_constants.sort();
+    public_constants
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -164,7 +262,7 @@ mod tests {
             }),
             referencing_pack: default_referencing_pack(),
             expected_violation: Some(build_expected_violation(
-                String::from("packs/foo/app/services/foo.rb:3:1\nPrivacy violation: `::Bar` is private to `packs/bar`, but referenced from `packs/foo`"),
+                String::from("Privacy violation: `::Bar` is private to `packs/bar`, but referenced from `packs/foo`"),
                 String::from("privacy"), false,
             )),
         };
@@ -185,7 +283,7 @@ mod tests {
             }),
             referencing_pack: default_referencing_pack(),
             expected_violation: Some(build_expected_violation(
-                String::from("packs/foo/app/services/foo.rb:3:1\nPrivacy violation: `::Bar` is private to `packs/bar`, but referenced from `packs/foo`"),
+                String::from("Privacy violation: `::Bar` is private to `packs/bar`, but referenced from `packs/foo`"),
                 String::from("privacy"), true,
             )),
         };
@@ -257,6 +355,7 @@ mod tests {
                     "packs/bar/app/public/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -285,6 +384,7 @@ mod tests {
                     "packs/bar/app/public/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -314,6 +414,7 @@ mod tests {
                     "packs/bar/app/api/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -344,6 +445,7 @@ mod tests {
                     "packs/bar/app/services/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -377,6 +479,7 @@ mod tests {
                     "packs/bar/app/services/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -389,11 +492,16 @@ mod tests {
                 ..default_defining_pack()
             }),
             referencing_pack: default_referencing_pack(),
-            expected_violation: Some(build_expected_violation_with_constant(
-                String::from("packs/foo/app/services/foo.rb:3:1\nPrivacy violation: `::Bar::BarChild` is private to `packs/bar`, but referenced from `packs/foo`"),
-                String::from("privacy"), false,
-                String::from("::Bar::BarChild")
-            )),
+            expected_violation: Some(Violation {
+                relative_defining_file: Some(String::from(
+                    "packs/bar/app/services/bar.rb",
+                )),
+                ..build_expected_violation_with_constant(
+                    String::from("Privacy violation: `::Bar::BarChild` is private to `packs/bar`, but referenced from `packs/foo`"),
+                    String::from("privacy"), false,
+                    String::from("::Bar::BarChild")
+                )
+            }),
             ..Default::default()
         };
         test_check(&Checker {}, &mut test_checker)
@@ -413,6 +521,7 @@ mod tests {
                     "packs/bar/app/api/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -446,6 +555,7 @@ mod tests {
                     "packs/bar/app/api/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -479,6 +589,7 @@ mod tests {
                     "packs/bar/app/public/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: Some(Pack {
@@ -497,6 +608,98 @@ mod tests {
         test_check(&Checker {}, &mut test_checker)
     }
 
+    #[test]
+    fn test_public_paths_include_defining_file() -> anyhow::Result<()> {
+        let mut test_checker = TestChecker {
+            referenced_constant_name: Some(String::from("::Bar")),
+            defining_pack: Some(Pack {
+                name: "packs/bar".to_owned(),
+                enforce_privacy: Some(CheckerSetting::True),
+                relative_path: PathBuf::from("packs/bar"),
+                public_paths: vec![String::from("app/*/public")],
+                ..default_defining_pack()
+            }),
+            ..Default::default()
+        };
+        test_check(&Checker {}, &mut test_checker)
+    }
+
+    fn defining_pack_with_public_constants() -> Pack {
+        Pack {
+            name: "packs/bar".to_owned(),
+            enforce_privacy: Some(CheckerSetting::True),
+            public_constants: HashSet::from([
+                String::from("::Bar"),
+                String::from("::Baz::Api::*"),
+            ]),
+            ..default_defining_pack()
+        }
+    }
+
+    #[test]
+    fn test_public_constants_include_referenced_constant() -> anyhow::Result<()>
+    {
+        for constant_name in ["::Bar", "::Baz::Api::Client"] {
+            let mut test_checker = TestChecker {
+                referenced_constant_name: Some(String::from(constant_name)),
+                defining_pack: Some(defining_pack_with_public_constants()),
+                ..Default::default()
+            };
+            test_check(&Checker {}, &mut test_checker)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_constants_do_not_include_referenced_constant(
+    ) -> anyhow::Result<()> {
+        let mut test_checker = TestChecker {
+            referenced_constant_name: Some(String::from("::Baz::Apis")),
+            defining_pack: Some(defining_pack_with_public_constants()),
+            expected_violation: Some(build_expected_violation_with_constant(
+                String::from("Privacy violation: `::Baz::Apis` is private to `packs/bar`, but referenced from `packs/foo`"),
+                String::from("privacy"),
+                false,
+                String::from("::Baz::Apis"),
+            )),
+            ..Default::default()
+        };
+        test_check(&Checker {}, &mut test_checker)
+    }
+
+    #[test]
+    fn test_validate_public_constant_patterns() {
+        let pack = Pack {
+            public_constants: HashSet::from([
+                String::from("::Bar"),
+                String::from("::Bar::*"),
+                String::from("::Baz*"),
+            ]),
+            relative_path: PathBuf::from("packs/bar"),
+            ..defining_pack_with_public_constants()
+        };
+        let configuration = Configuration {
+            pack_set: PackSet::build(
+                HashSet::from([
+                    pack,
+                    Pack {
+                        name: String::from("."),
+                        ..Pack::default()
+                    },
+                ]),
+                HashMap::new(),
+            )
+            .unwrap(),
+            ..Configuration::default()
+        };
+        assert_eq!(
+            Checker {}.validate(&configuration),
+            Some(vec![String::from(
+                "Invalid 'public_constants' in 'packs/bar/package.yml'. `::Baz*` must be a constant name, or a namespace ending in `::*`"
+            )])
+        );
+    }
+
     #[test]
     fn test_defining_pack_not_found() -> anyhow::Result<()> {
         let mut test_checker = TestChecker {
@@ -511,6 +714,7 @@ mod tests {
                     "packs/bar/app/public/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             }),
             configuration: None,
             defining_pack: None,
@@ -5,6 +5,7 @@ use anyhow::{bail, Context};
 use crate::packs::{
     constant_resolver::ConstantResolver, pack::Pack,
     parsing::UnresolvedReference, Configuration, PackSet, SourceLocation,
+    Suppression,
 };
 
 #[derive(Debug)]
@@ -15,6 +16,7 @@ pub struct Reference {
     pub referencing_pack_name: String,
     pub relative_referencing_file: String,
     pub source_location: SourceLocation,
+    pub suppressions: Vec<Suppression>,
 }
 
 impl Reference {
@@ -34,6 +36,19 @@ impl Reference {
         }
     }
 
+    // The `pks:disable-next-line` comment disabling this violation type, if any
+    pub fn suppression_for(
+        &self,
+        violation_type: &str,
+    ) -> Option<&Suppression> {
+        self.suppressions.iter().find(|suppression| {
+            suppression
+                .violation_types
+                .iter()
+                .any(|disabled| disabled == violation_type)
+        })
+    }
+
     pub fn referencing_pack<'a>(
         &self,
         pack_set: &'a PackSet,
@@ -116,6 +131,7 @@ impl Reference {
                             .clone(),
                         source_location: source_location.clone(),
                         relative_defining_file,
+                        suppressions: unresolved_reference.suppressions.clone(),
                     })
                 })
                 .collect::<anyhow::Result<Vec<Reference>>>()?)
@@ -132,6 +148,7 @@ impl Reference {
                 relative_referencing_file,
                 source_location,
                 relative_defining_file,
+                suppressions: unresolved_reference.suppressions.clone(),
             }])
         }
     }
@@ -0,0 +1,182 @@
+// Module declarations
+mod checkstyle;
+mod github;
+mod json;
+mod junit;
+mod sarif;
+pub(crate) mod text;
+
+// Internal imports
+use super::{
+    build_strict_violation_message, sorted_identifiers, sorted_violations,
+    CheckAllResult, ViolationIdentifier,
+};
+use crate::packs::bin_locater;
+use crate::packs::Configuration;
+use crate::packs::SourceLocation;
+
+// External imports
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+    /// GitHub Actions workflow annotations
+    Github,
+    Junit,
+    Checkstyle,
+}
+
+impl OutputFormat {
+    pub(crate) fn name(&self) -> String {
+        use clap::ValueEnum;
+        self.to_possible_value()
+            .map(|value| value.get_name().to_owned())
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) trait Reporter {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String>;
+
+    // `update` only finds recorded and strict mode violations, so most formats
+    // can report it the same way as `check`
+    fn report_update(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        self.report_check(result)
+    }
+}
+
+pub(crate) fn get_reporter<'a>(
+    configuration: &'a Configuration,
+) -> anyhow::Result<Box<dyn Reporter + 'a>> {
+    let reporter: Box<dyn Reporter + 'a> = match configuration.output_format {
+        OutputFormat::Text => Box::new(text::Reporter {
+            color: configuration.color,
+        }),
+        OutputFormat::Json => Box::new(json::Reporter {}),
+        OutputFormat::Sarif => Box::new(sarif::Reporter { configuration }),
+        OutputFormat::Github => Box::new(github::Reporter {}),
+        OutputFormat::Junit => Box::new(junit::Reporter {}),
+        OutputFormat::Checkstyle => Box::new(checkstyle::Reporter {}),
+    };
+    Ok(reporter)
+}
+
+const UNUSED_SUPPRESSION_TYPE: &str = "unused_suppression";
+const TODO_BUDGET_TYPE: &str = "todo_budget";
+
+// A single problem to report, for formats that don't distinguish between
+// reportable, stale and strict mode violations.
+struct Finding {
+    file: String,
+    source_location: Option<SourceLocation>,
+    violation_type: String,
+    message: String,
+}
+
+fn findings(result: &CheckAllResult) -> Vec<Finding> {
+    let reportable = sorted_violations(&result.reportable_violations)
+        .into_iter()
+        .map(|violation| Finding {
+            file: violation.identifier.file.clone(),
+            source_location: Some(violation.source_location.clone()),
+            violation_type: violation.identifier.violation_type.clone(),
+            message: violation.plain_message(),
+        });
+    let stale = sorted_identifiers(&result.stale_violations)
+        .into_iter()
+        .map(|identifier| Finding {
+            file: package_todo_file(identifier),
+            source_location: None,
+            violation_type: identifier.violation_type.clone(),
+            message: stale_violation_message(identifier),
+        });
+    let strict = sorted_violations(&result.strict_mode_violations)
+        .into_iter()
+        .map(|violation| Finding {
+            file: violation.identifier.file.clone(),
+            source_location: Some(violation.source_location.clone()),
+            violation_type: violation.identifier.violation_type.clone(),
+            message: build_strict_violation_message(&violation.identifier),
+        });
+    let unused_suppressions =
+        result
+            .unused_suppressions
+            .iter()
+            .map(|unused_suppression| Finding {
+                file: unused_suppression.file.clone(),
+                source_location: Some(SourceLocation {
+                    line: unused_suppression.line,
+                    column: 0,
+                }),
+                violation_type: String::from(UNUSED_SUPPRESSION_TYPE),
+                message: unused_suppression.message(),
+            });
+    let exceeded_todo_budgets =
+        result
+            .exceeded_todo_budgets
+            .iter()
+            .map(|exceeded_todo_budget| Finding {
+                file: exceeded_todo_budget.file.clone(),
+                source_location: None,
+                violation_type: String::from(TODO_BUDGET_TYPE),
+                message: exceeded_todo_budget.message(),
+            });
+    reportable
+        .chain(stale)
+        .chain(strict)
+        .chain(unused_suppressions)
+        .chain(exceeded_todo_budgets)
+        .collect()
+}
+
+// The package_todo.yml file a recorded violation lives in, relative to the
+// project root
+fn package_todo_file(identifier: &ViolationIdentifier) -> String {
+    let path =
+        Path::new(&identifier.referencing_pack_name).join("package_todo.yml");
+    path.strip_prefix(".")
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn stale_violation_message(identifier: &ViolationIdentifier) -> String {
+    format!(
+        "Stale {} violation on `{}` recorded for `{}`, please run `{} update`",
+        identifier.violation_type,
+        identifier.constant_name,
+        identifier.file,
+        bin_locater::packs_bin_name(),
+    )
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,50 @@
+// Writes violations in the checkstyle XML format understood by many code
+// review tools (e.g. reviewdog, Jenkins warnings).
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::{escape_xml, findings, Finding};
+use crate::packs::checker::CheckAllResult;
+
+pub struct Reporter {}
+
+impl super::Reporter for Reporter {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let mut findings_by_file: BTreeMap<String, Vec<Finding>> =
+            BTreeMap::new();
+        for finding in findings(result) {
+            findings_by_file
+                .entry(finding.file.clone())
+                .or_default()
+                .push(finding);
+        }
+
+        let mut output = String::new();
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(output, r#"<checkstyle version="4.3">"#)?;
+        for (file, findings) in findings_by_file {
+            writeln!(output, r#"  <file name="{}">"#, escape_xml(&file))?;
+            for finding in findings {
+                write!(output, "    <error")?;
+                if let Some(source_location) = &finding.source_location {
+                    write!(
+                        output,
+                        r#" line="{}" column="{}""#,
+                        source_location.line,
+                        // checkstyle columns are 1-based, ours are 0-based
+                        source_location.column + 1
+                    )?;
+                }
+                writeln!(
+                    output,
+                    r#" severity="error" message="{}" source="packs.{}"/>"#,
+                    escape_xml(&finding.message),
+                    escape_xml(&finding.violation_type),
+                )?;
+            }
+            writeln!(output, "  </file>")?;
+        }
+        write!(output, "</checkstyle>")?;
+        Ok(output)
+    }
+}
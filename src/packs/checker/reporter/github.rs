@@ -0,0 +1,60 @@
+// Writes violations as GitHub Actions workflow commands, which show up as
+// annotations on the pull request diff.
+// See: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#setting-an-error-message
+use std::fmt::Write;
+
+use super::findings;
+use crate::packs::checker::CheckAllResult;
+
+pub struct Reporter {}
+
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+impl super::Reporter for Reporter {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let mut output = String::new();
+        for finding in findings(result) {
+            write!(output, "::error file={}", escape_property(&finding.file))?;
+            if let Some(source_location) = &finding.source_location {
+                write!(
+                    output,
+                    ",line={},col={}",
+                    source_location.line,
+                    // GitHub columns are 1-based, ours are 0-based
+                    source_location.column + 1
+                )?;
+            }
+            writeln!(
+                output,
+                ",title={}::{}",
+                escape_property(&format!(
+                    "packs {} violation",
+                    finding.violation_type
+                )),
+                escape_data(&finding.message)
+            )?;
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_property() {
+        assert_eq!(
+            escape_property("a:b,c%d\ne"),
+            String::from("a%3Ab%2Cc%25d%0Ae")
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use serde::Serialize;
+
+use crate::packs::checker::{
+    sorted_identifiers, sorted_violations, CheckAllResult, ExceededTodoBudget,
+    UnusedSuppression, Violation, ViolationIdentifier,
+};
+use crate::packs::SourceLocation;
+
+pub struct Reporter {}
+
+// A violation as it appears in machine-readable output.
+// Identifier fields are flattened so consumers can match records against
+// package_todo.yml entries without knowing about the nesting.
+#[derive(Serialize)]
+struct ViolationRecord<'a> {
+    #[serde(flatten)]
+    identifier: &'a ViolationIdentifier,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_location: Option<&'a SourceLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    defining_file: Option<&'a String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl<'a> From<&'a Violation> for ViolationRecord<'a> {
+    fn from(violation: &'a Violation) -> Self {
+        Self {
+            identifier: &violation.identifier,
+            source_location: Some(&violation.source_location),
+            defining_file: violation.relative_defining_file.as_ref(),
+            message: Some(violation.plain_message()),
+        }
+    }
+}
+
+impl<'a> From<&'a ViolationIdentifier> for ViolationRecord<'a> {
+    fn from(identifier: &'a ViolationIdentifier) -> Self {
+        Self {
+            identifier,
+            source_location: None,
+            defining_file: None,
+            message: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CheckAllRecord<'a> {
+    reportable_violations: Vec<ViolationRecord<'a>>,
+    stale_violations: Vec<ViolationRecord<'a>>,
+    strict_mode_violations: Vec<ViolationRecord<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unused_suppressions: Vec<UnusedSuppressionRecord<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exceeded_todo_budgets: Vec<ExceededTodoBudgetRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct UnusedSuppressionRecord<'a> {
+    #[serde(flatten)]
+    unused_suppression: &'a UnusedSuppression,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct ExceededTodoBudgetRecord<'a> {
+    #[serde(flatten)]
+    exceeded_todo_budget: &'a ExceededTodoBudget,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct UpdateRecord<'a> {
+    recorded_violations: Vec<ViolationRecord<'a>>,
+    strict_mode_violations: Vec<ViolationRecord<'a>>,
+    // New violations `todo_ratchet` kept out of package_todo.yml files
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unrecorded_violations: Vec<ViolationRecord<'a>>,
+}
+
+fn violation_records<'a>(
+    violations: impl IntoIterator<Item = &'a Violation>,
+) -> Vec<ViolationRecord<'a>> {
+    sorted_violations(violations)
+        .into_iter()
+        .map(ViolationRecord::from)
+        .collect()
+}
+
+impl super::Reporter for Reporter {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let record = CheckAllRecord {
+            reportable_violations: violation_records(
+                &result.reportable_violations,
+            ),
+            stale_violations: sorted_identifiers(&result.stale_violations)
+                .into_iter()
+                .map(ViolationRecord::from)
+                .collect(),
+            strict_mode_violations: violation_records(
+                &result.strict_mode_violations,
+            ),
+            unused_suppressions: result
+                .unused_suppressions
+                .iter()
+                .map(|unused_suppression| UnusedSuppressionRecord {
+                    unused_suppression,
+                    message: unused_suppression.message(),
+                })
+                .collect(),
+            exceeded_todo_budgets: result
+                .exceeded_todo_budgets
+                .iter()
+                .map(|exceeded_todo_budget| ExceededTodoBudgetRecord {
+                    exceeded_todo_budget,
+                    message: exceeded_todo_budget.message(),
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string_pretty(&record)?)
+    }
+
+    fn report_update(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let record = UpdateRecord {
+            recorded_violations: violation_records(&result.recorded_violations),
+            strict_mode_violations: violation_records(
+                &result.strict_mode_violations,
+            ),
+            unrecorded_violations: violation_records(
+                &result.reportable_violations,
+            ),
+        };
+        Ok(serde_json::to_string_pretty(&record)?)
+    }
+}
@@ -0,0 +1,71 @@
+// Writes violations as a JUnit XML report, with one failing test case per
+// violation, so CI systems can show them alongside test results.
+use std::fmt::Write;
+
+use super::{escape_xml, findings};
+use crate::packs::checker::CheckAllResult;
+
+pub struct Reporter {}
+
+impl super::Reporter for Reporter {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let findings = findings(result);
+        let mut output = String::new();
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        if findings.is_empty() {
+            writeln!(
+                output,
+                r#"<testsuites name="packs" tests="1" failures="0">"#
+            )?;
+            writeln!(
+                output,
+                r#"  <testsuite name="packs" tests="1" failures="0">"#
+            )?;
+            writeln!(
+                output,
+                r#"    <testcase name="check" classname="packs"/>"#
+            )?;
+        } else {
+            writeln!(
+                output,
+                r#"<testsuites name="packs" tests="{}" failures="{}">"#,
+                findings.len(),
+                findings.len()
+            )?;
+            writeln!(
+                output,
+                r#"  <testsuite name="packs" tests="{}" failures="{}">"#,
+                findings.len(),
+                findings.len()
+            )?;
+            for finding in findings {
+                let name = match &finding.source_location {
+                    Some(source_location) => format!(
+                        "{}:{}:{}",
+                        finding.file,
+                        source_location.line,
+                        source_location.column
+                    ),
+                    None => finding.file.clone(),
+                };
+                writeln!(
+                    output,
+                    r#"    <testcase name="{}" classname="{}" file="{}">"#,
+                    escape_xml(&name),
+                    escape_xml(&finding.file),
+                    escape_xml(&finding.file),
+                )?;
+                writeln!(
+                    output,
+                    r#"      <failure type="{}" message="{}"/>"#,
+                    escape_xml(&finding.violation_type),
+                    escape_xml(&finding.message),
+                )?;
+                writeln!(output, "    </testcase>")?;
+            }
+        }
+        writeln!(output, "  </testsuite>")?;
+        write!(output, "</testsuites>")?;
+        Ok(output)
+    }
+}
@@ -0,0 +1,350 @@
+// Writes violations as a SARIF 2.1.0 log, the format consumed by most
+// code-scanning dashboards.
+// See: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use super::{package_todo_file, stale_violation_message};
+use crate::packs::checker::pack_checker::ViolationType;
+use crate::packs::checker::{
+    get_checkers, sorted_identifiers, sorted_violations, CheckAllResult,
+    ExceededTodoBudget, UnusedSuppression, Violation, ViolationIdentifier,
+};
+use crate::packs::Configuration;
+
+pub struct Reporter<'a> {
+    pub configuration: &'a Configuration,
+}
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SRCROOT: &str = "%SRCROOT%";
+const STALE_RULE_ID: &str = "packs/stale_violation";
+const UNUSED_SUPPRESSION_RULE_ID: &str = "packs/unused_suppression";
+const TODO_BUDGET_RULE_ID: &str = "packs/todo_budget";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    original_uri_base_ids: BTreeMap<&'static str, ArtifactLocation>,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    name: String,
+    short_description: Message,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<Suppression>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+}
+
+#[derive(Serialize)]
+struct Suppression {
+    kind: &'static str,
+    justification: &'static str,
+}
+
+struct SarifBuilder {
+    rules: Vec<Rule>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifBuilder {
+    fn new(configuration: &Configuration) -> Self {
+        let mut rules: Vec<Rule> = get_checkers(configuration)
+            .iter()
+            .map(|checker| {
+                let violation_type = checker.violation_type();
+                Rule {
+                    id: rule_id(&violation_type),
+                    name: violation_type.clone(),
+                    short_description: Message {
+                        text: rule_description(&violation_type).to_owned(),
+                    },
+                }
+            })
+            .collect();
+        rules.push(Rule {
+            id: STALE_RULE_ID.to_owned(),
+            name: String::from("stale_violation"),
+            short_description: Message {
+                text: String::from("A package_todo.yml entry no longer matches a violation in the codebase"),
+            },
+        });
+        rules.push(Rule {
+            id: UNUSED_SUPPRESSION_RULE_ID.to_owned(),
+            name: String::from("unused_suppression"),
+            short_description: Message {
+                text: String::from("A pks:disable-next-line comment does not disable any violation"),
+            },
+        });
+        rules.push(Rule {
+            id: TODO_BUDGET_RULE_ID.to_owned(),
+            name: String::from("todo_budget"),
+            short_description: Message {
+                text: String::from("A package_todo.yml records more violations than the pack's max_todo_violations"),
+            },
+        });
+        Self {
+            rules,
+            results: Vec::new(),
+        }
+    }
+
+    fn rule_index(&self, rule_id: &str) -> usize {
+        self.rules
+            .iter()
+            .position(|rule| rule.id == rule_id)
+            .unwrap_or_else(|| panic!("Unknown SARIF rule: {}", rule_id))
+    }
+
+    fn add_violation(&mut self, violation: &Violation, suppressed: bool) {
+        let rule_id = rule_id(&violation.identifier.violation_type);
+        let suppressions = if suppressed {
+            vec![Suppression {
+                kind: "external",
+                justification: "Recorded in package_todo.yml",
+            }]
+        } else {
+            vec![]
+        };
+        self.results.push(SarifResult {
+            rule_index: self.rule_index(&rule_id),
+            rule_id,
+            level: "error",
+            message: Message {
+                text: violation.plain_message(),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: violation.identifier.file.clone(),
+                        uri_base_id: Some(SRCROOT),
+                    },
+                    region: Some(Region {
+                        start_line: violation.source_location.line,
+                        // SARIF columns are 1-based, ours are 0-based
+                        start_column: violation.source_location.column + 1,
+                    }),
+                },
+            }],
+            suppressions,
+        });
+    }
+
+    fn add_stale_violation(&mut self, identifier: &ViolationIdentifier) {
+        self.results.push(SarifResult {
+            rule_id: STALE_RULE_ID.to_owned(),
+            rule_index: self.rule_index(STALE_RULE_ID),
+            level: "error",
+            message: Message {
+                text: stale_violation_message(identifier),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: package_todo_file(identifier),
+                        uri_base_id: Some(SRCROOT),
+                    },
+                    region: None,
+                },
+            }],
+            suppressions: vec![],
+        });
+    }
+
+    fn add_unused_suppression(
+        &mut self,
+        unused_suppression: &UnusedSuppression,
+    ) {
+        self.results.push(SarifResult {
+            rule_id: UNUSED_SUPPRESSION_RULE_ID.to_owned(),
+            rule_index: self.rule_index(UNUSED_SUPPRESSION_RULE_ID),
+            level: "error",
+            message: Message {
+                text: unused_suppression.message(),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: unused_suppression.file.clone(),
+                        uri_base_id: Some(SRCROOT),
+                    },
+                    region: Some(Region {
+                        start_line: unused_suppression.line,
+                        start_column: 1,
+                    }),
+                },
+            }],
+            suppressions: vec![],
+        });
+    }
+
+    fn add_exceeded_todo_budget(
+        &mut self,
+        exceeded_todo_budget: &ExceededTodoBudget,
+    ) {
+        self.results.push(SarifResult {
+            rule_id: TODO_BUDGET_RULE_ID.to_owned(),
+            rule_index: self.rule_index(TODO_BUDGET_RULE_ID),
+            level: "error",
+            message: Message {
+                text: exceeded_todo_budget.message(),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: exceeded_todo_budget.file.clone(),
+                        uri_base_id: Some(SRCROOT),
+                    },
+                    region: None,
+                },
+            }],
+            suppressions: vec![],
+        });
+    }
+
+    fn build(self, configuration: &Configuration) -> anyhow::Result<String> {
+        let mut root_uri =
+            format!("file://{}", configuration.absolute_root.to_string_lossy());
+        if !root_uri.ends_with('/') {
+            root_uri.push('/');
+        }
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "packs",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules: self.rules,
+                    },
+                },
+                original_uri_base_ids: BTreeMap::from([(
+                    SRCROOT,
+                    ArtifactLocation {
+                        uri: root_uri,
+                        uri_base_id: None,
+                    },
+                )]),
+                results: self.results,
+            }],
+        };
+        Ok(serde_json::to_string_pretty(&log)?)
+    }
+}
+
+fn rule_id(violation_type: &str) -> String {
+    format!("packs/{}", violation_type)
+}
+
+fn rule_description(violation_type: &str) -> &'static str {
+    match ViolationType::from(violation_type) {
+        ViolationType::Dependency => "A pack references a constant from a pack it does not declare as a dependency",
+        ViolationType::FolderPrivacy => "A pack references a constant from a pack that is not a sibling or parent",
+        ViolationType::ForbiddenDependency => "A pack references a constant from a pack that `forbidden_dependencies` forbids it to depend on",
+        ViolationType::Layer => "A pack references a constant from a pack in a layer it may not depend on",
+        ViolationType::Privacy => "A pack references a private constant of another pack",
+        ViolationType::Visibility => "A pack references a constant from a pack that is not visible to it",
+    }
+}
+
+impl<'a> super::Reporter for Reporter<'a> {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let mut builder = SarifBuilder::new(self.configuration);
+
+        let mut unsuppressed: HashSet<&Violation> =
+            result.reportable_violations.iter().collect();
+        unsuppressed.extend(result.strict_mode_violations.iter());
+        for violation in sorted_violations(unsuppressed) {
+            builder.add_violation(violation, false);
+        }
+        for violation in sorted_violations(&result.recorded_violations) {
+            builder.add_violation(violation, true);
+        }
+        for identifier in sorted_identifiers(&result.stale_violations) {
+            builder.add_stale_violation(identifier);
+        }
+        for unused_suppression in &result.unused_suppressions {
+            builder.add_unused_suppression(unused_suppression);
+        }
+        for exceeded_todo_budget in &result.exceeded_todo_budgets {
+            builder.add_exceeded_todo_budget(exceeded_todo_budget);
+        }
+
+        builder.build(self.configuration)
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt::Write;
+
+use crate::packs::bin_locater;
+use crate::packs::checker::{
+    build_strict_violation_message, sorted_violations, CheckAllResult,
+    Violation,
+};
+
+pub struct Reporter {
+    pub color: bool,
+}
+
+impl Reporter {
+    fn file(&self, file: &str) -> String {
+        if self.color {
+            format!("\x1b[36m{}\x1b[0m", file)
+        } else {
+            file.to_owned()
+        }
+    }
+
+    fn location(&self, violation: &Violation) -> String {
+        format!(
+            "{}:{}:{}",
+            self.file(&violation.identifier.file),
+            violation.source_location.line,
+            violation.source_location.column,
+        )
+    }
+
+    fn write_violations(
+        &self,
+        result: &CheckAllResult,
+        f: &mut String,
+    ) -> std::fmt::Result {
+        if !result.reportable_violations.is_empty() {
+            let sorted_violations =
+                sorted_violations(&result.reportable_violations);
+
+            writeln!(f, "{} violation(s) detected:", sorted_violations.len())?;
+
+            for violation in sorted_violations {
+                writeln!(
+                    f,
+                    "{}\n{}\n",
+                    self.location(violation),
+                    violation.message
+                )?;
+            }
+        }
+
+        if !result.stale_violations.is_empty() {
+            writeln!(
+                f,
+                "There were stale violations found, please run `{} update`",
+                bin_locater::packs_bin_name(),
+            )?;
+        }
+
+        for violation in result.strict_mode_violations.iter() {
+            writeln!(
+                f,
+                "{}",
+                build_strict_violation_message(&violation.identifier)
+            )?;
+        }
+
+        for exceeded_todo_budget in &result.exceeded_todo_budgets {
+            writeln!(
+                f,
+                "{}\n{}\n",
+                self.file(&exceeded_todo_budget.file),
+                exceeded_todo_budget.message()
+            )?;
+        }
+
+        if !result.unused_suppressions.is_empty() {
+            writeln!(
+                f,
+                "{} unused suppression(s) detected:",
+                result.unused_suppressions.len()
+            )?;
+            for unused_suppression in &result.unused_suppressions {
+                writeln!(
+                    f,
+                    "{}:{}\n{}\n",
+                    self.file(&unused_suppression.file),
+                    unused_suppression.line,
+                    unused_suppression.message()
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // What changed since the previous check, for `check --watch`
+    pub(crate) fn report_delta(
+        &self,
+        new_violations: Vec<&Violation>,
+        fixed_violations: Vec<&Violation>,
+    ) -> anyhow::Result<String> {
+        if new_violations.is_empty() && fixed_violations.is_empty() {
+            return Ok(String::from("No new or fixed violations"));
+        }
+        let mut output = String::new();
+        for (heading, violations) in
+            [("new", new_violations), ("fixed", fixed_violations)]
+        {
+            if violations.is_empty() {
+                continue;
+            }
+            writeln!(output, "{} {} violation(s):", violations.len(), heading)?;
+            for violation in sorted_violations(violations) {
+                writeln!(
+                    output,
+                    "{}\n{}\n",
+                    self.location(violation),
+                    violation.message
+                )?;
+            }
+        }
+        Ok(output.trim_end().to_owned())
+    }
+}
+
+impl super::Reporter for Reporter {
+    fn report_check(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        if !result.has_violations() {
+            return Ok(String::from("No violations detected!"));
+        }
+        let mut output = String::new();
+        self.write_violations(result, &mut output)?;
+        Ok(output)
+    }
+
+    fn report_update(&self, result: &CheckAllResult) -> anyhow::Result<String> {
+        let mut output = String::new();
+        let strict_violations = &result.strict_mode_violations;
+        if !strict_violations.is_empty() {
+            for violation in strict_violations {
+                writeln!(
+                    output,
+                    "{}",
+                    build_strict_violation_message(&violation.identifier)
+                )?;
+            }
+            writeln!(
+                output,
+                "{} strict mode violation(s) detected. These violations must be fixed for `check` to succeed.",
+                strict_violations.len()
+            )?;
+        }
+        let unrecorded_violations = &result.reportable_violations;
+        if !unrecorded_violations.is_empty() {
+            writeln!(
+                output,
+                "{} new violation(s) were not recorded because `todo_ratchet` is enabled in packwerk.yml:",
+                unrecorded_violations.len()
+            )?;
+            for violation in sorted_violations(unrecorded_violations) {
+                writeln!(
+                    output,
+                    "{}\n{}\n",
+                    self.location(violation),
+                    violation.message
+                )?;
+            }
+        }
+        write!(output, "Successfully updated package_todo.yml files!")?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::packs::checker::reporter::Reporter as _;
+    use crate::packs::checker::{
+        CheckAllResult, Violation, ViolationIdentifier,
+    };
+    use crate::packs::SourceLocation;
+
+    fn check_result() -> CheckAllResult {
+        CheckAllResult {
+            reportable_violations: vec![
+                Violation {
+                    message: "Privacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`".to_string(),
+                    identifier: ViolationIdentifier {
+                        violation_type: "Privacy".to_string(),
+                        strict: false,
+                        file: "foo/bar/file1.rb".to_string(),
+                        constant_name: "::Foo::PrivateClass".to_string(),
+                        referencing_pack_name: "bar".to_string(),
+                        defining_pack_name: "foo".to_string(),
+                    },
+                    source_location: SourceLocation { line: 10, column: 5 },
+                    relative_defining_file: Some("foo/app/services/private_class.rb".to_string()),
+                },
+                Violation {
+                    message: "Dependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`".to_string(),
+                    identifier: ViolationIdentifier {
+                        violation_type: "Dependency".to_string(),
+                        strict: false,
+                        file: "foo/bar/file2.rb".to_string(),
+                        constant_name: "::Foo::AnotherClass".to_string(),
+                        referencing_pack_name: "foo".to_string(),
+                        defining_pack_name: "bar".to_string(),
+                    },
+                    source_location: SourceLocation { line: 15, column: 3 },
+                    relative_defining_file: Some("bar/app/services/some_class.rb".to_string()),
+                }
+            ].iter().cloned().collect(),
+            stale_violations: Vec::new(),
+            strict_mode_violations: Vec::new(),
+            recorded_violations: Vec::new(),
+            unused_suppressions: Vec::new(),
+            exceeded_todo_budgets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_write_violations() {
+        let expected_output = "2 violation(s) detected:
+foo/bar/file1.rb:10:5
+Privacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`
+
+foo/bar/file2.rb:15:3
+Dependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`
+
+";
+
+        let reporter = super::Reporter { color: false };
+        let actual = reporter.report_check(&check_result()).unwrap();
+
+        assert_eq!(actual, expected_output);
+    }
+
+    #[test]
+    fn test_write_violations_with_color() {
+        let reporter = super::Reporter { color: true };
+        let actual = reporter.report_check(&check_result()).unwrap();
+
+        assert!(actual.contains("\x1b[36mfoo/bar/file1.rb\x1b[0m:10:5\n"));
+    }
+
+    #[test]
+    fn test_report_delta() {
+        let result = check_result();
+        let mut violations: Vec<&Violation> =
+            result.reportable_violations.iter().collect();
+        violations.sort_by_key(|violation| &violation.identifier.file);
+        let reporter = super::Reporter { color: false };
+
+        let actual = reporter
+            .report_delta(vec![violations[1]], vec![violations[0]])
+            .unwrap();
+        assert_eq!(
+            actual,
+            "1 new violation(s):
+foo/bar/file2.rb:15:3
+Dependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`
+
+1 fixed violation(s):
+foo/bar/file1.rb:10:5
+Privacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`"
+        );
+
+        let actual = reporter.report_delta(vec![], vec![]).unwrap();
+        assert_eq!(actual, "No new or fixed violations");
+    }
+}
@@ -0,0 +1,185 @@
+// `max_todo_violations` in package.yml caps how many violations a pack's
+// package_todo.yml may record, so teams can't keep adding to it.
+use serde::Serialize;
+
+use crate::packs::pack::{MaxTodoViolations, Pack};
+use crate::packs::Configuration;
+
+#[derive(PartialEq, Clone, Eq, Hash, Debug, Serialize)]
+pub struct ExceededTodoBudget {
+    pub pack_name: String,
+    // The package_todo.yml file, relative to the project root
+    pub file: String,
+    // Unset for a budget covering every violation type
+    pub violation_type: Option<String>,
+    pub recorded: usize,
+    pub max: usize,
+}
+
+impl ExceededTodoBudget {
+    pub(crate) fn message(&self) -> String {
+        let violations = match &self.violation_type {
+            Some(violation_type) => format!("{} violation(s)", violation_type),
+            None => String::from("violation(s)"),
+        };
+        format!(
+            "`{}` records {} {} in package_todo.yml, but its `max_todo_violations` allows {}",
+            self.pack_name, self.recorded, violations, self.max
+        )
+    }
+}
+
+pub(crate) fn exceeded_todo_budgets(
+    configuration: &Configuration,
+) -> Vec<ExceededTodoBudget> {
+    let mut packs: Vec<&Pack> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .filter(|pack| pack.max_todo_violations.is_some())
+        .collect();
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut exceeded_budgets = vec![];
+    for pack in packs {
+        let violations = pack.all_violations();
+        let budgets: Vec<(Option<&String>, usize)> =
+            match pack.max_todo_violations.as_ref().unwrap() {
+                MaxTodoViolations::Total(max) => vec![(None, *max)],
+                MaxTodoViolations::PerViolationType(maxes) => maxes
+                    .iter()
+                    .map(|(violation_type, max)| (Some(violation_type), *max))
+                    .collect(),
+            };
+        for (violation_type, max) in budgets {
+            let recorded = violations
+                .iter()
+                .filter(|violation| {
+                    violation_type
+                        .map_or(true, |t| &violation.violation_type == t)
+                })
+                .count();
+            if recorded > max {
+                exceeded_budgets.push(ExceededTodoBudget {
+                    pack_name: pack.name.clone(),
+                    file: pack
+                        .relative_path
+                        .join("package_todo.yml")
+                        .to_string_lossy()
+                        .to_string(),
+                    violation_type: violation_type.cloned(),
+                    recorded,
+                    max,
+                });
+            }
+        }
+    }
+    exceeded_budgets
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::path::PathBuf;
+
+    use crate::packs::package_todo::ViolationGroup;
+    use crate::packs::{PackSet, PackageTodo};
+
+    use super::*;
+
+    fn configuration_with_budget(
+        max_todo_violations: MaxTodoViolations,
+    ) -> Configuration {
+        // Two dependency violations and one privacy violation
+        let package_todo = PackageTodo {
+            violations_by_defining_pack: BTreeMap::from([(
+                String::from("packs/bar"),
+                BTreeMap::from([
+                    (
+                        String::from("::Bar"),
+                        ViolationGroup {
+                            violation_types: HashSet::from([
+                                String::from("dependency"),
+                                String::from("privacy"),
+                            ]),
+                            files: HashSet::from([String::from(
+                                "packs/foo/app/services/foo.rb",
+                            )]),
+                            ..ViolationGroup::default()
+                        },
+                    ),
+                    (
+                        String::from("::BarBlah"),
+                        ViolationGroup {
+                            violation_types: HashSet::from([String::from(
+                                "dependency",
+                            )]),
+                            files: HashSet::from([String::from(
+                                "packs/foo/app/services/foo.rb",
+                            )]),
+                            ..ViolationGroup::default()
+                        },
+                    ),
+                ]),
+            )]),
+        };
+        let packs = vec![
+            Pack {
+                name: String::from("."),
+                ..Pack::default()
+            },
+            Pack {
+                name: String::from("packs/foo"),
+                relative_path: PathBuf::from("packs/foo"),
+                max_todo_violations: Some(max_todo_violations),
+                package_todo,
+                ..Pack::default()
+            },
+        ];
+        Configuration {
+            pack_set: PackSet::build(HashSet::from_iter(packs), HashMap::new())
+                .unwrap(),
+            ..Configuration::default()
+        }
+    }
+
+    #[test]
+    fn test_total_budget() {
+        assert_eq!(
+            exceeded_todo_budgets(&configuration_with_budget(
+                MaxTodoViolations::Total(2)
+            )),
+            vec![ExceededTodoBudget {
+                pack_name: String::from("packs/foo"),
+                file: String::from("packs/foo/package_todo.yml"),
+                violation_type: None,
+                recorded: 3,
+                max: 2,
+            }]
+        );
+        assert!(exceeded_todo_budgets(&configuration_with_budget(
+            MaxTodoViolations::Total(3)
+        ))
+        .is_empty());
+    }
+
+    #[test]
+    fn test_budget_per_violation_type() {
+        let exceeded_budgets =
+            exceeded_todo_budgets(&configuration_with_budget(
+                MaxTodoViolations::PerViolationType(BTreeMap::from([
+                    (String::from("dependency"), 1),
+                    (String::from("privacy"), 1),
+                ])),
+            ));
+        assert_eq!(
+            exceeded_budgets
+                .iter()
+                .map(ExceededTodoBudget::message)
+                .collect::<Vec<_>>(),
+            vec![String::from(
+                "`packs/foo` records 2 dependency violation(s) in package_todo.yml, but its `max_todo_violations` allows 1"
+            )]
+        );
+    }
+}
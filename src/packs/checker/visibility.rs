@@ -1,6 +1,3 @@
-use std::collections::HashSet;
-
-use super::output_helper::print_reference_location;
 use super::pack_checker::PackChecker;
 use super::CheckerInterface;
 use crate::packs::checker::Reference;
@@ -23,29 +20,18 @@ impl CheckerInterface for Checker {
             return Ok(None);
         }
         let defining_pack = pack_checker.defining_pack.unwrap();
-        if defining_pack
-            .visible_to
-            .as_ref()
-            .unwrap_or(&HashSet::new())
-            .contains(&pack_checker.referencing_pack.name)
-        {
+        if defining_pack.is_visible_to(&pack_checker.referencing_pack.name) {
             return Ok(None);
         }
 
-        let loc = print_reference_location(reference);
-
         let message = format!(
-            "{}Visibility violation: `{}` belongs to `{}`, which is not visible to `{}`",
-            loc,
+            "Visibility violation: `{}` belongs to `{}`, which is not visible to `{}`",
             reference.constant_name,
             defining_pack.name,
             pack_checker.referencing_pack.name,
         );
 
-        Ok(Some(Violation {
-            message,
-            identifier: pack_checker.violation_identifier(),
-        }))
+        Ok(Some(pack_checker.violation(message)))
     }
 
     fn violation_type(&self) -> String {
@@ -107,7 +93,7 @@ mod tests {
                 relative_path: PathBuf::from("packs/foo"),
                 ..default_referencing_pack()},
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo`".to_string(),
+                "Visibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo`".to_string(),
                 "visibility".to_string(), false)),
         };
         test_check(&Checker {}, &mut test_checker)
@@ -159,7 +145,7 @@ mod tests {
                 relative_path: PathBuf::from("packs/foo"),
                 ..default_referencing_pack()},
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo`".to_string(),
+                "Visibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo`".to_string(),
                 "visibility".to_string(), true)),
         };
         test_check(&Checker {}, &mut test_checker)
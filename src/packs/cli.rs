@@ -1,9 +1,16 @@
 use crate::packs;
 
+use crate::packs::checker::reporter::OutputFormat;
+use crate::packs::configuration::use_color;
 use crate::packs::file_utils::get_absolute_path;
+use crate::packs::graph::GraphFormat;
+use crate::packs::metrics::MetricsFormat;
+use crate::packs::package_todo::parse_days;
+use crate::packs::server;
+use crate::packs::stats::StatsFormat;
 use clap::{Parser, Subcommand};
 use clap_derive::Args;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
 use super::logger::install_logger;
@@ -77,6 +84,22 @@ enum Command {
         #[arg(long)]
         ignore_recorded_violations: bool,
 
+        /// Output format for reported violations
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Only check files added, modified or renamed since this git ref (e.g. origin/main)
+        #[arg(long, conflicts_with = "files")]
+        changed_since: Option<String>,
+
+        /// Report `pks:disable-next-line` comments that don't disable any violation
+        #[arg(long)]
+        report_unused_disables: bool,
+
+        /// Keep running and re-check files as they change, printing new and fixed violations
+        #[arg(long, conflicts_with_all = ["files", "changed_since"])]
+        watch: bool,
+
         files: Vec<String>,
     },
 
@@ -86,17 +109,41 @@ enum Command {
         #[arg(long)]
         ignore_recorded_violations: bool,
 
+        /// Output format for reported violations
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
         file: String,
     },
 
     #[clap(
         about = "Update package_todo.yml files with the current violations"
     )]
-    Update,
+    Update {
+        /// Output format for reported violations
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    #[clap(
+        about = "Start a language server over stdio that reports violations as diagnostics"
+    )]
+    Lsp,
+
+    #[clap(
+        about = "Keep the project loaded and answer requests on a Unix socket, which `check` and `check-contents` use when it's running"
+    )]
+    Server,
 
     #[clap(about = "Look for validation errors in the codebase")]
     Validate,
 
+    #[clap(about = "Inspect package_todo.yml entries")]
+    Todo {
+        #[command(subcommand)]
+        command: TodoCommand,
+    },
+
     #[clap(about = "Add a dependency from one pack to another")]
     AddDependency {
         /// The pack that depends on another pack
@@ -106,6 +153,29 @@ enum Command {
         to: String,
     },
 
+    #[clap(
+        about = "Move files or directories into another pack, updating package_todo.yml files"
+    )]
+    Move {
+        /// Files or directories to move
+        #[arg(required = true, num_args = 1..)]
+        paths: Vec<String>,
+
+        /// The pack to move them into
+        destination: String,
+    },
+
+    #[clap(
+        about = "Rename a pack, updating every package.yml and package_todo.yml that refers to it"
+    )]
+    RenamePack {
+        /// The current name of the pack
+        old_name: String,
+
+        /// The new name of the pack
+        new_name: String,
+    },
+
     #[clap(
         about = "Add missing dependencies for the pack that defines the constant"
     )]
@@ -146,6 +216,47 @@ enum Command {
         pack: String,
     },
 
+    #[clap(about = "Export the pack dependency graph")]
+    Graph {
+        /// Output format for the graph
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+
+        /// Include dependencies recorded in package_todo.yml files
+        #[arg(long)]
+        implicit: bool,
+
+        /// Only include packs near this pack
+        #[arg(long)]
+        focus: Option<String>,
+
+        /// How many edges away from the focused pack to include
+        #[arg(long, default_value_t = 1, requires = "focus")]
+        depth: usize,
+    },
+
+    #[clap(
+        about = "Compute coupling, instability and abstractness metrics for each pack"
+    )]
+    Metrics {
+        /// Output format for the metrics
+        #[arg(long, value_enum, default_value_t = MetricsFormat::Csv)]
+        format: MetricsFormat,
+    },
+
+    #[clap(
+        about = "Count the violations recorded in package_todo.yml files by pack, owner, violation type and defining pack"
+    )]
+    Stats {
+        /// Output format for the counts
+        #[arg(long, value_enum, default_value_t = StatsFormat::Text)]
+        format: StatsFormat,
+
+        /// Also count the violations recorded in each of the last N commits
+        #[arg(long, value_name = "N")]
+        git_history: Option<usize>,
+    },
+
     #[clap(
         about = "List analyzed files based on configuration in packwerk.yml (for debugging purposes)"
     )]
@@ -157,6 +268,30 @@ enum Command {
     ListDefinitions(ListDefinitionsArgs),
 }
 
+impl Command {
+    // Only the commands that report violations take a reporter `--format`
+    fn output_format(&self) -> OutputFormat {
+        match self {
+            Command::Check { format, .. }
+            | Command::CheckContents { format, .. }
+            | Command::Update { format } => *format,
+            _ => OutputFormat::default(),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum TodoCommand {
+    #[clap(
+        about = "List package_todo.yml entries that have expired or expire soon"
+    )]
+    Expiring {
+        /// How far ahead to look, in days (e.g. 30d) or weeks (e.g. 2w)
+        #[arg(long, default_value = "30d", value_parser = parse_days)]
+        within: i64,
+    },
+}
+
 #[derive(Debug, Args)]
 struct ListDefinitionsArgs {
     /// Show constants with multiple definitions only
@@ -183,6 +318,55 @@ impl Args {
             .canonicalize()
             .map_err(anyhow::Error::from)
     }
+
+    fn server_settings(&self) -> server::Settings {
+        server::Settings {
+            experimental_parser: self.experimental_parser,
+            disable_enforce_dependencies: self.disable_enforce_dependencies,
+            disable_enforce_folder_privacy: self.disable_enforce_folder_privacy,
+            disable_enforce_layers: self.disable_enforce_layers,
+            disable_enforce_privacy: self.disable_enforce_privacy,
+            disable_enforce_visibility: self.disable_enforce_visibility,
+        }
+    }
+}
+
+// A running `packs server` already has the configuration loaded, which is
+// most of what checking a few files costs. Returns `None` when the command
+// has to run here.
+fn check_with_server(
+    args: &Args,
+    absolute_root: &Path,
+) -> Option<anyhow::Result<()>> {
+    let connect =
+        || server::Client::connect(absolute_root, &args.server_settings());
+    let options = |ignore_recorded_violations: bool| server::CheckOptions {
+        ignore_recorded_violations,
+        format: args.command.output_format(),
+        color: use_color(),
+    };
+    match &args.command {
+        Command::Check {
+            ignore_recorded_violations,
+            format: _,
+            changed_since: None,
+            report_unused_disables: false,
+            watch: false,
+            files,
+        } => Some(
+            connect()?
+                .check(files.clone(), options(*ignore_recorded_violations)),
+        ),
+        Command::CheckContents {
+            ignore_recorded_violations,
+            file,
+            ..
+        } => Some(connect()?.check_contents(
+            file.clone(),
+            options(*ignore_recorded_violations),
+        )),
+        _ => None,
+    }
 }
 
 pub fn run() -> anyhow::Result<()> {
@@ -201,6 +385,10 @@ pub fn run() -> anyhow::Result<()> {
         packs::init(&absolute_root, use_packwerk)?
     }
 
+    if let Some(result) = check_with_server(&args, &absolute_root) {
+        return result;
+    }
+
     let mut configuration = packs::configuration::get(&absolute_root)?;
 
     if args.print_files {
@@ -217,6 +405,8 @@ pub fn run() -> anyhow::Result<()> {
         configuration.cache_enabled = false;
     }
 
+    configuration.output_format = args.command.output_format();
+
     if args.disable_enforce_dependencies {
         configuration.disable_enforce_dependencies = true;
     }
@@ -256,21 +446,49 @@ pub fn run() -> anyhow::Result<()> {
         Command::ListPackDependencies { pack } => {
             packs::list_dependencies(&configuration, pack)
         }
+        Command::Graph {
+            format,
+            implicit,
+            focus,
+            depth,
+        } => packs::graph(&configuration, format, implicit, focus, depth),
         Command::AddDependency { from, to } => {
             packs::add_dependency(&configuration, from, to)
         }
+        Command::Move { paths, destination } => {
+            packs::move_files(&configuration, paths, destination)
+        }
+        Command::RenamePack { old_name, new_name } => {
+            packs::rename_pack(&configuration, old_name, new_name)
+        }
+        Command::Metrics { format } => packs::metrics(&configuration, format),
+        Command::Stats {
+            format,
+            git_history,
+        } => packs::stats(&configuration, format, git_history),
         Command::ListIncludedFiles => packs::list_included_files(configuration),
         Command::Check {
             ignore_recorded_violations,
+            changed_since,
+            report_unused_disables,
+            watch,
             files,
+            ..
         } => {
             configuration.ignore_recorded_violations =
                 ignore_recorded_violations;
-            packs::check(&configuration, files)
+            configuration.changed_since = changed_since;
+            configuration.report_unused_disables = report_unused_disables;
+            if watch {
+                packs::watch(configuration)
+            } else {
+                packs::check(&configuration, files)
+            }
         }
         Command::CheckContents {
             ignore_recorded_violations,
             file,
+            ..
         } => {
             configuration.ignore_recorded_violations =
                 ignore_recorded_violations;
@@ -279,11 +497,16 @@ pub fn run() -> anyhow::Result<()> {
             configuration.stdin_file_path = Some(absolute_path);
             packs::check(&configuration, vec![file])
         }
-        Command::Update => packs::update(&configuration),
+        Command::Update { .. } => packs::update(&configuration),
+        Command::Lsp => packs::run_language_server(configuration),
+        Command::Server => packs::run_server(configuration),
         Command::Validate => {
             packs::validate(&configuration)
             // Err("💡 Please use `packs check` to detect dependency cycles and run other configuration validations".into())
         }
+        Command::Todo {
+            command: TodoCommand::Expiring { within },
+        } => packs::list_expiring_todos(&configuration, within),
         Command::CheckUnnecessaryDependencies { auto_correct } => {
             packs::check_unnecessary_dependencies(&configuration, auto_correct)
         }
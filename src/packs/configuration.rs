@@ -1,9 +1,14 @@
 use super::caching::{
     cache::Cache, create_cache_dir_idempotently, noop_cache::NoopCache,
-    per_file_cache::PerFileCache,
+    per_file_cache::PerFileCache, versioned_cache_dir,
 };
+use super::checker::forbidden_dependency::ForbiddenDependency;
 use super::checker::layer::Layers;
-use super::file_utils::user_inputted_paths_to_absolute_filepaths;
+use super::checker::reporter::OutputFormat;
+use super::file_utils::{
+    build_glob_set, user_inputted_paths_to_absolute_filepaths,
+};
+use super::pack::Pack;
 
 use super::{
     constant_resolver::ConstantResolverConfiguration, raw_configuration,
@@ -11,7 +16,9 @@ use super::{
     walk_directory::WalkDirectoryResult, PackSet,
 };
 
+use globset::GlobSet;
 use std::collections::HashMap;
+use std::io::IsTerminal;
 use std::{
     collections::HashSet,
     path::{Path, PathBuf},
@@ -19,6 +26,14 @@ use std::{
 use tracing::debug;
 use walk_directory::walk_directory;
 
+// Changing any of these files changes which violations exist
+pub(crate) const CONFIGURATION_FILE_NAMES: [&str; 4] = [
+    "package.yml",
+    "package_todo.yml",
+    "packwerk.yml",
+    "packs.yml",
+];
+
 pub struct Configuration {
     pub included_files: HashSet<PathBuf>,
     pub absolute_root: PathBuf,
@@ -26,6 +41,11 @@ pub struct Configuration {
     pub cache_directory: PathBuf,
     pub pack_set: PackSet,
     pub layers: Layers,
+    pub forbidden_dependencies: Vec<ForbiddenDependency>,
+    // Matches test files by their path relative to their pack
+    pub test_paths: GlobSet,
+    // Only let `update` remove entries from package_todo.yml files
+    pub todo_ratchet: bool,
     pub experimental_parser: bool,
     pub ignored_definitions: HashMap<String, HashSet<PathBuf>>,
     pub autoload_roots: HashMap<PathBuf, String>,
@@ -38,6 +58,12 @@ pub struct Configuration {
     pub print_files: bool,
     pub packs_first_mode: bool,
     pub ignore_recorded_violations: bool,
+    // Only check files changed since this git ref
+    pub changed_since: Option<String>,
+    // Report `pks:disable-next-line` comments that don't disable anything
+    pub report_unused_disables: bool,
+    pub output_format: OutputFormat,
+    pub color: bool,
     pub disable_enforce_dependencies: bool,
     pub disable_enforce_folder_privacy: bool,
     pub disable_enforce_layers: bool,
@@ -64,13 +90,56 @@ impl Configuration {
         }
     }
 
+    // Re-reads configuration files from disk (e.g. after a package.yml
+    // changed), keeping the options that were set from the command line
+    pub(crate) fn reload(&self) -> anyhow::Result<Configuration> {
+        let configuration = get(&self.absolute_root)?;
+        Ok(Configuration {
+            experimental_parser: self.experimental_parser,
+            cache_enabled: self.cache_enabled,
+            stdin_file_path: self.stdin_file_path.clone(),
+            print_files: self.print_files,
+            ignore_recorded_violations: self.ignore_recorded_violations,
+            changed_since: self.changed_since.clone(),
+            report_unused_disables: self.report_unused_disables,
+            output_format: self.output_format,
+            color: self.color,
+            disable_enforce_dependencies: self.disable_enforce_dependencies,
+            disable_enforce_folder_privacy: self.disable_enforce_folder_privacy,
+            disable_enforce_layers: self.disable_enforce_layers,
+            disable_enforce_privacy: self.disable_enforce_privacy,
+            disable_enforce_visibility: self.disable_enforce_visibility,
+            ..configuration
+        })
+    }
+
+    // Whether the file, relative to the project root, is one of the pack's
+    // test files
+    pub(crate) fn is_test_file(
+        &self,
+        pack: &Pack,
+        relative_file: &str,
+    ) -> bool {
+        let relative_file = Path::new(relative_file);
+        let path_in_pack = if pack.relative_path == Path::new(".") {
+            relative_file
+        } else {
+            match relative_file.strip_prefix(&pack.relative_path) {
+                Ok(path_in_pack) => path_in_pack,
+                Err(_) => return false,
+            }
+        };
+        self.test_paths.is_match(path_in_pack)
+    }
+
     pub(crate) fn get_cache(&self) -> Box<dyn Cache + Send + Sync> {
         if self.cache_enabled {
-            let cache_dir = if self.experimental_parser {
+            let parser_cache_dir = if self.experimental_parser {
                 self.cache_directory.join("experimental")
             } else {
                 self.cache_directory.join("zeitwerk")
             };
+            let cache_dir = versioned_cache_dir(&parser_cache_dir);
 
             create_cache_dir_idempotently(&cache_dir);
 
@@ -121,9 +190,13 @@ pub(crate) fn from_raw(
     let cache_enabled = raw_config.cache;
     let experimental_parser = raw_config.experimental_parser;
 
-    let layers = Layers {
-        layers: raw_config.layers,
-    };
+    let layers = Layers::from(raw_config.layers);
+    let test_paths = build_glob_set(&raw_config.test_paths);
+    let forbidden_dependencies = raw_config
+        .forbidden_dependencies
+        .into_iter()
+        .map(ForbiddenDependency::new)
+        .collect::<anyhow::Result<Vec<_>>>()?;
 
     let ignored_definitions = raw_config.ignored_definitions;
     let autoload_roots: HashMap<PathBuf, String> = raw_config.autoload_roots;
@@ -152,6 +225,9 @@ pub(crate) fn from_raw(
         cache_directory,
         pack_set,
         layers,
+        forbidden_dependencies,
+        test_paths,
+        todo_ratchet: raw_config.todo_ratchet,
         experimental_parser,
         ignored_definitions,
         autoload_roots,
@@ -161,6 +237,10 @@ pub(crate) fn from_raw(
         print_files: false,
         packs_first_mode,
         ignore_recorded_violations: false,
+        changed_since: None,
+        report_unused_disables: false,
+        output_format: OutputFormat::default(),
+        color: use_color(),
         disable_enforce_dependencies: false,
         disable_enforce_folder_privacy: false,
         disable_enforce_layers: false,
@@ -169,6 +249,11 @@ pub(crate) fn from_raw(
     })
 }
 
+// Colors are for people reading stdout in a terminal
+pub(crate) fn use_color() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -210,6 +295,7 @@ mod tests {
                 enforce_folder_privacy: None,
                 enforce_folder_visibility: None,
                 enforce_layers: None,
+                enforce_acyclic_references: None,
                 owner: None,
                 yml: absolute_root.join("packs/bar/package.yml"),
                 name: String::from("packs/bar"),
@@ -217,10 +303,14 @@ mod tests {
                 dependencies: HashSet::new(),
                 visible_to: None,
                 package_todo: PackageTodo::default(),
+                dev_dependencies: HashSet::new(),
                 ignored_dependencies: HashSet::new(),
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
+                public_constants: HashSet::new(),
+                max_todo_violations: None,
                 public_folder: None,
+                public_paths: vec![],
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
@@ -232,6 +322,7 @@ mod tests {
                 enforce_folder_privacy: None,
                 enforce_folder_visibility: None,
                 enforce_layers: None,
+                enforce_acyclic_references: None,
                 owner: None,
                 yml: absolute_root.join("packs/baz/package.yml"),
                 name: String::from("packs/baz"),
@@ -239,10 +330,14 @@ mod tests {
                 dependencies: HashSet::new(),
                 visible_to: None,
                 package_todo: PackageTodo::default(),
+                dev_dependencies: HashSet::new(),
                 ignored_dependencies: HashSet::new(),
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
+                public_constants: HashSet::new(),
+                max_todo_violations: None,
                 public_folder: None,
+                public_paths: vec![],
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
@@ -254,6 +349,7 @@ mod tests {
                 enforce_folder_privacy: None,
                 enforce_folder_visibility: None,
                 enforce_layers: None,
+                enforce_acyclic_references: None,
                 owner: None,
                 yml: absolute_root.join("packs/foo/package.yml"),
                 name: String::from("packs/foo"),
@@ -263,10 +359,14 @@ mod tests {
                 )]),
                 visible_to: None,
                 package_todo: PackageTodo::default(),
+                dev_dependencies: HashSet::new(),
                 ignored_dependencies: HashSet::new(),
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
+                public_constants: HashSet::new(),
+                max_todo_violations: None,
                 public_folder: None,
+                public_paths: vec![],
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
@@ -278,6 +378,7 @@ mod tests {
                 enforce_folder_privacy: None,
                 enforce_folder_visibility: None,
                 enforce_layers: None,
+                enforce_acyclic_references: None,
                 owner: None,
                 yml: absolute_root.join("package.yml"),
                 name: String::from("."),
@@ -285,10 +386,14 @@ mod tests {
                 dependencies: HashSet::new(),
                 visible_to: None,
                 package_todo: PackageTodo::default(),
+                dev_dependencies: HashSet::new(),
                 ignored_dependencies: HashSet::new(),
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
+                public_constants: HashSet::new(),
+                max_todo_violations: None,
                 public_folder: None,
+                public_paths: vec![],
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
@@ -383,4 +488,22 @@ mod tests {
 
         assert_eq!(actual_associations, expected_paths);
     }
+
+    #[test]
+    fn test_is_test_file() {
+        let configuration = Configuration::default();
+        let pack = Pack {
+            relative_path: PathBuf::from("packs/foo"),
+            ..Pack::default()
+        };
+        let root_pack = Pack {
+            relative_path: PathBuf::from("."),
+            ..Pack::default()
+        };
+        assert!(configuration.is_test_file(&pack, "packs/foo/spec/foo_spec.rb"));
+        assert!(configuration.is_test_file(&pack, "packs/foo/test/a/b.rb"));
+        assert!(!configuration.is_test_file(&pack, "packs/foo/app/spec.rb"));
+        assert!(!configuration.is_test_file(&pack, "spec/foo_spec.rb"));
+        assert!(configuration.is_test_file(&root_pack, "spec/foo_spec.rb"));
+    }
 }
@@ -52,7 +52,7 @@ fn find_pack_names_for_update<'a>(
                 .for_pack(referencing_pack_name)
                 .context("Could not find the referencing pack")
                 .ok()?;
-            if referencing_pack.dependencies.contains(defining_pack_name) {
+            if referencing_pack.depends_on(defining_pack_name) {
                 None
             } else {
                 Some(referencing_pack)
@@ -114,6 +114,7 @@ mod tests {
                     "packs/bar/app/api/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             },
             Reference {
                 constant_name: String::from("::Bar::BarChild"),
@@ -126,6 +127,7 @@ mod tests {
                     "packs/bar/app/api/bar.rb",
                 )),
                 source_location: SourceLocation { line: 3, column: 1 },
+                suppressions: vec![],
             },
             Reference {
                 constant_name: String::from("::BarChild"),
@@ -141,6 +143,7 @@ mod tests {
                     line: 33,
                     column: 1,
                 },
+                suppressions: vec![],
             },
             Reference {
                 constant_name: String::from("::Bar"),
@@ -156,6 +159,7 @@ mod tests {
                     line: 53,
                     column: 1,
                 },
+                suppressions: vec![],
             },
         ]
     }
@@ -22,42 +22,60 @@ pub fn find_dependencies(
         .pack_set
         .packs
         .iter()
-        .filter(|p| p.name != pack.name && p.dependencies.contains(&pack.name))
+        .filter(|p| p.name != pack.name && p.depends_on(&pack.name))
         .map(|p| p.name.clone())
         .collect();
     public_dependencies.sort();
 
+    let implicit_dependencies = find_all_implicit_dependencies(configuration)
+        .into_iter()
+        .filter(|((referencing_pack_name, defining_pack_name), _)| {
+            defining_pack_name == &pack.name
+                && referencing_pack_name != &pack.name
+        })
+        .map(|((referencing_pack_name, _), counts)| {
+            (referencing_pack_name, counts)
+        })
+        .collect();
+
+    Ok(Dependencies {
+        explicit: public_dependencies,
+        implicit: implicit_dependencies,
+    })
+}
+
+// Dependencies recorded in package_todo.yml files, keyed by the referencing
+// and the defining pack. Each recorded constant counts once per violation type.
+pub fn find_all_implicit_dependencies(
+    configuration: &Configuration,
+) -> HashMap<(PackName, PackName), HashMap<ViolationType, ViolationCount>> {
     let mut implicit_dependencies: HashMap<
-        PackName,
+        (PackName, PackName),
         HashMap<ViolationType, ViolationCount>,
     > = HashMap::new();
 
     for current_pack in &configuration.pack_set.packs {
-        if current_pack.name != pack.name {
-            for (violation_pack_name, violation_groups) in
-                &current_pack.package_todo.violations_by_defining_pack
-            {
-                if violation_pack_name == &pack.name {
-                    for violation_group in violation_groups.values() {
-                        let entry = implicit_dependencies
-                            .entry(current_pack.name.clone())
-                            .or_default();
-                        for violation_type in &violation_group.violation_types {
-                            entry
-                                .entry(violation_type.clone())
-                                .and_modify(|e| *e += 1)
-                                .or_insert(1);
-                        }
-                    }
+        for (violation_pack_name, violation_groups) in
+            &current_pack.package_todo.violations_by_defining_pack
+        {
+            for violation_group in violation_groups.values() {
+                let entry = implicit_dependencies
+                    .entry((
+                        current_pack.name.clone(),
+                        violation_pack_name.clone(),
+                    ))
+                    .or_default();
+                for violation_type in &violation_group.violation_types {
+                    entry
+                        .entry(violation_type.clone())
+                        .and_modify(|e| *e += 1)
+                        .or_insert(1);
                 }
             }
         }
     }
 
-    Ok(Dependencies {
-        explicit: public_dependencies,
-        implicit: implicit_dependencies,
-    })
+    implicit_dependencies
 }
 
 #[cfg(test)]
@@ -0,0 +1,324 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+use super::checker::ViolationIdentifier;
+use super::file_utils::{expand_glob, get_absolute_path};
+use super::pack::Pack;
+use super::package_todo::write_violation_identifiers_to_disk;
+use super::reference_extractor::{get_all_references, get_constant_resolver};
+use super::{bin_locater, Configuration};
+
+#[derive(Debug, PartialEq, Eq)]
+struct FileMove {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+// Pairs of (referencing pack, defining pack) for every cross-pack reference
+type PackEdges = BTreeSet<(String, String)>;
+
+pub(crate) fn move_files(
+    configuration: &Configuration,
+    paths: Vec<String>,
+    destination_pack_name: String,
+) -> anyhow::Result<()> {
+    let destination_pack = configuration
+        .pack_set
+        .for_pack(&destination_pack_name)
+        .context(format!("`{}` not found", destination_pack_name))?;
+
+    let file_moves = plan_file_moves(configuration, paths, destination_pack)?;
+    if file_moves.is_empty() {
+        bail!("No files to move");
+    }
+
+    let moved_constants = constants_defined_in(configuration, &file_moves)?;
+    let edges_before = pack_edges(configuration)?;
+
+    // Either every file moves or none does, so package_todo.yml files never
+    // describe a half-finished move
+    let mut done: Vec<&FileMove> = Vec::new();
+    for file_move in &file_moves {
+        if let Err(error) = move_file(file_move) {
+            let not_moved_back = roll_back(&done);
+            if not_moved_back.is_empty() {
+                return Err(error.context("No files were moved"));
+            }
+            bail!(
+                "{:#}. These files were moved but could not be moved back: {}",
+                error,
+                not_moved_back
+                    .iter()
+                    .map(|file_move| relative_path(
+                        configuration,
+                        &file_move.to
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+        }
+        done.push(file_move);
+    }
+    for file_move in &file_moves {
+        println!(
+            "Moved {} to {}",
+            relative_path(configuration, &file_move.from),
+            relative_path(configuration, &file_move.to)
+        );
+    }
+
+    rewrite_package_todos(
+        configuration,
+        &file_moves,
+        &moved_constants,
+        &destination_pack.name,
+    );
+
+    let new_configuration = configuration.reload()?;
+    let edges_after = pack_edges(&new_configuration)?;
+    report_dependency_changes(&new_configuration, &edges_before, &edges_after);
+
+    Ok(())
+}
+
+fn move_file(file_move: &FileMove) -> anyhow::Result<()> {
+    let context = format!("Failed to move {}", file_move.from.display());
+    if let Some(parent) = file_move.to.parent() {
+        fs::create_dir_all(parent).context(context.clone())?;
+    }
+    fs::rename(&file_move.from, &file_move.to).context(context)
+}
+
+// Moves files back, newest move first. Returns the moves that couldn't be
+// undone.
+fn roll_back<'a>(done: &[&'a FileMove]) -> Vec<&'a FileMove> {
+    done.iter()
+        .rev()
+        .filter(|file_move| fs::rename(&file_move.to, &file_move.from).is_err())
+        .copied()
+        .collect()
+}
+
+fn plan_file_moves(
+    configuration: &Configuration,
+    paths: Vec<String>,
+    destination_pack: &Pack,
+) -> anyhow::Result<Vec<FileMove>> {
+    let destination_root = pack_root(destination_pack);
+    let mut file_moves = Vec::new();
+    for path in paths {
+        let absolute_path = get_absolute_path(path.clone(), configuration);
+        let files = if absolute_path.is_dir() {
+            expand_glob(absolute_path.join("**/*").to_str().unwrap())
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect()
+        } else if absolute_path.is_file() {
+            vec![absolute_path]
+        } else {
+            bail!("`{}` does not exist", path);
+        };
+
+        for from in files {
+            let from_pack = owning_pack(configuration, &from)?;
+            if from_pack.name == destination_pack.name {
+                bail!(
+                    "`{}` already belongs to `{}`",
+                    relative_path(configuration, &from),
+                    destination_pack.name
+                );
+            }
+            // Keep the path within the pack (e.g. app/models/foo.rb) so the
+            // file stays in the same autoload root
+            let path_in_pack = from.strip_prefix(pack_root(from_pack))?;
+            let to = destination_root.join(path_in_pack);
+            if to.exists() {
+                bail!("`{}` already exists", relative_path(configuration, &to));
+            }
+            file_moves.push(FileMove { from, to });
+        }
+    }
+    Ok(file_moves)
+}
+
+fn pack_root(pack: &Pack) -> &Path {
+    pack.yml.parent().unwrap()
+}
+
+// The most deeply nested pack containing the file
+fn owning_pack<'a>(
+    configuration: &'a Configuration,
+    absolute_path: &Path,
+) -> anyhow::Result<&'a Pack> {
+    configuration
+        .pack_set
+        .packs
+        .iter()
+        .filter(|pack| absolute_path.starts_with(pack_root(pack)))
+        .max_by_key(|pack| pack_root(pack).components().count())
+        .context(format!(
+            "`{}` does not belong to any pack",
+            absolute_path.display()
+        ))
+}
+
+fn relative_path(
+    configuration: &Configuration,
+    absolute_path: &Path,
+) -> String {
+    absolute_path
+        .strip_prefix(&configuration.absolute_root)
+        .unwrap_or(absolute_path)
+        .to_string_lossy()
+        .to_string()
+}
+
+// Constants that only moved files define. A constant that is also defined
+// elsewhere, e.g. a namespace a moved file reopens, stays where it is.
+fn constants_defined_in(
+    configuration: &Configuration,
+    file_moves: &[FileMove],
+) -> anyhow::Result<HashSet<String>> {
+    let moved_files: HashSet<&PathBuf> =
+        file_moves.iter().map(|file_move| &file_move.from).collect();
+    let constant_resolver = get_constant_resolver(configuration)?;
+    Ok(constant_resolver
+        .fully_qualified_constant_name_to_constant_definition_map()
+        .iter()
+        .filter(|(_, definitions)| {
+            definitions.iter().all(|definition| {
+                moved_files.contains(&definition.absolute_path_of_definition)
+            })
+        })
+        .map(|(constant_name, _)| constant_name.clone())
+        .collect())
+}
+
+// Recorded violations follow the moved files: references from a moved file
+// now come from the destination pack, and references to constants defined
+// in a moved file now point at the destination pack.
+fn rewrite_package_todos(
+    configuration: &Configuration,
+    file_moves: &[FileMove],
+    moved_constants: &HashSet<String>,
+    destination_pack_name: &str,
+) {
+    let new_relative_paths: HashMap<String, String> = file_moves
+        .iter()
+        .map(|file_move| {
+            (
+                relative_path(configuration, &file_move.from),
+                relative_path(configuration, &file_move.to),
+            )
+        })
+        .collect();
+
+    let mut changed_pack_names: HashSet<String> = HashSet::new();
+    let mut identifiers: Vec<ViolationIdentifier> = Vec::new();
+    for identifier in &configuration.pack_set.all_violations {
+        let mut new_identifier = identifier.clone();
+        if let Some(new_path) = new_relative_paths.get(&identifier.file) {
+            new_identifier.file = new_path.clone();
+            new_identifier.referencing_pack_name =
+                destination_pack_name.to_owned();
+        }
+        if moved_constants.contains(&identifier.constant_name) {
+            new_identifier.defining_pack_name =
+                destination_pack_name.to_owned();
+        }
+        if &new_identifier == identifier {
+            identifiers.push(new_identifier);
+            continue;
+        }
+        changed_pack_names.insert(identifier.referencing_pack_name.clone());
+        // A pack can't violate its own boundaries
+        if new_identifier.referencing_pack_name
+            != new_identifier.defining_pack_name
+        {
+            changed_pack_names
+                .insert(new_identifier.referencing_pack_name.clone());
+            identifiers.push(new_identifier);
+        }
+    }
+
+    if !changed_pack_names.is_empty() {
+        write_violation_identifiers_to_disk(
+            configuration,
+            &changed_pack_names,
+            identifiers,
+        );
+        let mut changed_pack_names: Vec<&String> =
+            changed_pack_names.iter().collect();
+        changed_pack_names.sort();
+        for pack_name in changed_pack_names {
+            println!("Updated package_todo.yml for `{}`", pack_name);
+        }
+    }
+}
+
+fn pack_edges(configuration: &Configuration) -> anyhow::Result<PackEdges> {
+    let references =
+        get_all_references(configuration, &configuration.included_files)?;
+    Ok(references
+        .into_iter()
+        .filter_map(|reference| {
+            let defining_pack_name = reference.defining_pack_name?;
+            if defining_pack_name == reference.referencing_pack_name {
+                None
+            } else {
+                Some((reference.referencing_pack_name, defining_pack_name))
+            }
+        })
+        .collect())
+}
+
+fn report_dependency_changes(
+    configuration: &Configuration,
+    edges_before: &PackEdges,
+    edges_after: &PackEdges,
+) {
+    let pack_set = &configuration.pack_set;
+    let bin_name = bin_locater::packs_bin_name();
+
+    for (from, to) in edges_after.difference(edges_before) {
+        let Ok(from_pack) = pack_set.for_pack(from) else {
+            continue;
+        };
+        if from_pack.depends_on(to) || from_pack.ignores_dependency_on(to) {
+            continue;
+        }
+        if from_pack.dev_depends_on(to) {
+            println!(
+                "`{}` now references `{}`, which is only a dev dependency. If files other than tests reference it, run `{} add-dependency {} {}` to add a dependency",
+                from, to, bin_name, from, to
+            );
+        } else {
+            println!(
+                "`{}` now references `{}`, run `{} add-dependency {} {}` to add a dependency",
+                from, to, bin_name, from, to
+            );
+        }
+    }
+
+    for (from, to) in edges_before.difference(edges_after) {
+        let Ok(from_pack) = pack_set.for_pack(from) else {
+            continue;
+        };
+        // Dependencies declared by a glob stay, since the glob is about
+        // other packs too
+        if from_pack.depends_on(to) && from_pack.dependency_glob(to).is_none() {
+            println!(
+                "`{}` no longer references `{}`, its dependency on `{}` can be removed",
+                from, to, to
+            );
+        } else if from_pack.dev_dependencies.contains(to) {
+            println!(
+                "`{}` no longer references `{}`, its dev dependency on `{}` can be removed",
+                from, to, to
+            );
+        }
+    }
+}
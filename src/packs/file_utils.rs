@@ -8,12 +8,13 @@ use std::{
 use crate::packs::Configuration;
 use anyhow::Context;
 use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
-use regex::Regex;
 
 #[derive(PartialEq, Debug)]
 pub enum SupportedFileType {
     Ruby,
     Erb,
+    Haml,
+    Slim,
 }
 
 pub fn get_file_type(path: &Path) -> Option<SupportedFileType> {
@@ -31,11 +32,17 @@ pub fn get_file_type(path: &Path) -> Option<SupportedFileType> {
         || ruby_special_files.iter().any(|file| path.ends_with(file));
 
     let is_erb_file = path.extension().map_or(false, |ext| ext == "erb");
+    let is_haml_file = path.extension().map_or(false, |ext| ext == "haml");
+    let is_slim_file = path.extension().map_or(false, |ext| ext == "slim");
 
     if is_ruby_file {
         Some(SupportedFileType::Ruby)
     } else if is_erb_file {
         Some(SupportedFileType::Erb)
+    } else if is_haml_file {
+        Some(SupportedFileType::Haml)
+    } else if is_slim_file {
+        Some(SupportedFileType::Slim)
     } else {
         None
     }
@@ -101,20 +108,6 @@ pub fn user_inputted_paths_to_absolute_filepaths(
         .collect::<HashSet<_>>()
 }
 
-pub(crate) fn convert_erb_to_ruby_without_sourcemaps(
-    contents: String,
-) -> String {
-    let regex_pattern = r"(?s)<%=?-?\s*(.*?)\s*-?%>";
-    let regex = Regex::new(regex_pattern).unwrap();
-
-    let extracted_contents: Vec<&str> = regex
-        .captures_iter(&contents)
-        .map(|capture| capture.get(1).unwrap().as_str())
-        .collect();
-
-    extracted_contents.join("\n")
-}
-
 pub(crate) fn file_content_digest(file: &Path) -> anyhow::Result<String> {
     let mut file_content = Vec::new();
 
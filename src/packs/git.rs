@@ -0,0 +1,260 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ChangedFiles {
+    // Added, modified, copied and renamed files (at their new path)
+    pub changed: Vec<String>,
+    // Deleted files, including the old path of renamed files
+    pub deleted: Vec<String>,
+}
+
+// Files that changed between the merge base of `git_ref` and the working
+// tree, relative to `absolute_root`. Untracked files that aren't ignored
+// count as added, since `git diff` doesn't list them.
+pub(crate) fn changed_files_since(
+    absolute_root: &Path,
+    git_ref: &str,
+) -> anyhow::Result<ChangedFiles> {
+    let merge_base = run_git(absolute_root, &["merge-base", git_ref, "HEAD"])?;
+    let name_status = run_git(
+        absolute_root,
+        &[
+            "diff",
+            "--name-status",
+            "--find-renames",
+            "--relative",
+            "-z",
+            merge_base.trim(),
+        ],
+    )?;
+    let mut changed_files = parse_name_status(&name_status);
+    let untracked_files = run_git(
+        absolute_root,
+        &["ls-files", "--others", "--exclude-standard", "-z", "."],
+    )?;
+    changed_files.changed.extend(
+        untracked_files
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .map(str::to_owned),
+    );
+    Ok(changed_files)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Commit {
+    pub sha: String,
+    // Committer date, in strict ISO 8601 format
+    pub date: String,
+}
+
+// The last `count` commits on HEAD's first-parent chain, newest first, so
+// the commits of merged branches are skipped
+pub(crate) fn recent_commits(
+    absolute_root: &Path,
+    count: usize,
+) -> anyhow::Result<Vec<Commit>> {
+    let log = run_git(
+        absolute_root,
+        &[
+            "log",
+            "--first-parent",
+            "-n",
+            &count.to_string(),
+            "--format=%H %cI",
+        ],
+    )?;
+    Ok(log
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(sha, date)| Commit {
+            sha: sha.to_owned(),
+            date: date.to_owned(),
+        })
+        .collect())
+}
+
+// Files in `commit` under `absolute_root`, relative to it
+pub(crate) fn files_at_commit(
+    absolute_root: &Path,
+    commit: &str,
+) -> anyhow::Result<Vec<String>> {
+    let files = run_git(
+        absolute_root,
+        &["ls-tree", "-r", "-z", "--name-only", commit, "."],
+    )?;
+    Ok(files
+        .split('\0')
+        .filter(|file| !file.is_empty())
+        .map(str::to_owned)
+        .collect())
+}
+
+// The contents of files at a commit, read in one `git cat-file` process.
+// Files relative to `absolute_root` that don't exist in the commit are None.
+pub(crate) fn read_files_at_commit(
+    absolute_root: &Path,
+    commit: &str,
+    files: &[String],
+) -> anyhow::Result<Vec<Option<String>>> {
+    let mut input = String::new();
+    for file in files {
+        input.push_str(&format!("{}:./{}\n", commit, file));
+    }
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .current_dir(absolute_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run `git`, is it installed?")?;
+    let mut stdin = child.stdin.take().unwrap();
+    // Write from another thread so a full stdout pipe can't block us
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output()?;
+    writer
+        .join()
+        .map_err(|_| anyhow::anyhow!("Failed to write to `git cat-file`"))??;
+    if !output.status.success() {
+        bail!(
+            "`git cat-file --batch` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_cat_file_batch(&output.stdout, files.len())
+}
+
+// Parses the output of `git cat-file --batch`: for each object a
+// `<sha> <type> <size>` line, the contents and a newline, or a
+// `<object> missing` line
+fn parse_cat_file_batch(
+    output: &[u8],
+    count: usize,
+) -> anyhow::Result<Vec<Option<String>>> {
+    let mut contents = Vec::with_capacity(count);
+    let mut rest = output;
+    for _ in 0..count {
+        let header_end = rest
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .context("Unexpected end of `git cat-file` output")?;
+        let header = String::from_utf8_lossy(&rest[..header_end]);
+        rest = &rest[header_end + 1..];
+        if header.ends_with(" missing") || header.ends_with(" ambiguous") {
+            contents.push(None);
+            continue;
+        }
+        let size: usize = header
+            .rsplit(' ')
+            .next()
+            .and_then(|size| size.parse().ok())
+            .with_context(|| {
+                format!("Unexpected `git cat-file` header: {}", header)
+            })?;
+        if rest.len() < size + 1 {
+            bail!("Unexpected end of `git cat-file` output");
+        }
+        contents.push(Some(String::from_utf8_lossy(&rest[..size]).to_string()));
+        rest = &rest[size + 1..];
+    }
+    Ok(contents)
+}
+
+fn run_git(absolute_root: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(absolute_root)
+        .output()
+        .context("Failed to run `git`, is it installed?")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Parses the output of `git diff --name-status -z`, which is a sequence of
+// NUL-terminated fields: a status followed by one path, or two paths for
+// renames and copies.
+fn parse_name_status(output: &str) -> ChangedFiles {
+    let mut changed_files = ChangedFiles::default();
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    while let Some(status) = fields.next() {
+        match status.chars().next() {
+            Some('R') => {
+                if let (Some(old), Some(new)) = (fields.next(), fields.next()) {
+                    changed_files.deleted.push(old.to_owned());
+                    changed_files.changed.push(new.to_owned());
+                }
+            }
+            Some('C') => {
+                if let (Some(_source), Some(new)) =
+                    (fields.next(), fields.next())
+                {
+                    changed_files.changed.push(new.to_owned());
+                }
+            }
+            Some('D') => {
+                if let Some(path) = fields.next() {
+                    changed_files.deleted.push(path.to_owned());
+                }
+            }
+            _ => {
+                if let Some(path) = fields.next() {
+                    changed_files.changed.push(path.to_owned());
+                }
+            }
+        }
+    }
+    changed_files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_name_status() {
+        let output = "M\0packs/foo/app/services/foo.rb\0A\0packs/bar/new.rb\0D\0packs/baz/gone.rb\0R100\0packs/foo/old.rb\0packs/bar/moved.rb\0C75\0a.rb\0b.rb\0";
+
+        assert_eq!(
+            parse_name_status(output),
+            ChangedFiles {
+                changed: vec![
+                    String::from("packs/foo/app/services/foo.rb"),
+                    String::from("packs/bar/new.rb"),
+                    String::from("packs/bar/moved.rb"),
+                    String::from("b.rb"),
+                ],
+                deleted: vec![
+                    String::from("packs/baz/gone.rb"),
+                    String::from("packs/foo/old.rb"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_name_status() {
+        assert_eq!(parse_name_status(""), ChangedFiles::default());
+    }
+
+    #[test]
+    fn test_parse_cat_file_batch() {
+        let output = b"abc123 blob 5\nfoo:\n\nHEAD:./gone.yml missing\ndef456 blob 0\n\n";
+
+        assert_eq!(
+            parse_cat_file_batch(output, 3).unwrap(),
+            vec![Some(String::from("foo:\n")), None, Some(String::new())]
+        );
+        assert!(parse_cat_file_batch(output, 4).is_err());
+    }
+}
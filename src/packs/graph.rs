@@ -0,0 +1,326 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use petgraph::prelude::{DiGraph, NodeIndex};
+use serde::Serialize;
+
+use super::dependencies::find_all_implicit_dependencies;
+use super::Configuration;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+struct Edge {
+    // Listed in the `dependencies` of the referencing pack
+    declared: bool,
+    // Violation type => number of constants recorded in package_todo.yml
+    violations: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+    #[serde(flatten)]
+    edge: &'a Edge,
+}
+
+#[derive(Serialize)]
+struct JsonGraph<'a> {
+    nodes: Vec<JsonNode<'a>>,
+    edges: Vec<JsonEdge<'a>>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    Json,
+}
+
+pub(crate) struct GraphOptions {
+    pub format: GraphFormat,
+    pub implicit: bool,
+    pub focus: Option<String>,
+    pub depth: usize,
+}
+
+struct PackGraph {
+    graph: DiGraph<String, Edge>,
+}
+
+pub(crate) fn graph(
+    configuration: &Configuration,
+    options: GraphOptions,
+) -> anyhow::Result<String> {
+    let mut pack_graph = build_pack_graph(configuration, options.implicit);
+    if let Some(focus) = &options.focus {
+        let pack = configuration.pack_set.for_pack(focus)?;
+        pack_graph = pack_graph.neighborhood(&pack.name, options.depth);
+    }
+
+    match options.format {
+        GraphFormat::Dot => Ok(pack_graph.to_dot()),
+        GraphFormat::Mermaid => Ok(pack_graph.to_mermaid()),
+        GraphFormat::Json => pack_graph.to_json(),
+    }
+}
+
+fn build_pack_graph(
+    configuration: &Configuration,
+    implicit: bool,
+) -> PackGraph {
+    let pack_names: HashSet<&String> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .map(|pack| &pack.name)
+        .collect();
+
+    let mut edges: BTreeMap<(String, String), Edge> = BTreeMap::new();
+    for pack in &configuration.pack_set.packs {
+        for dependency in &pack.dependencies {
+            for to_pack in configuration.pack_set.matching_packs(dependency) {
+                if to_pack.name != pack.name {
+                    edges
+                        .entry((pack.name.clone(), to_pack.name.clone()))
+                        .or_default()
+                        .declared = true;
+                }
+            }
+        }
+    }
+    if implicit {
+        for ((from, to), counts) in
+            find_all_implicit_dependencies(configuration)
+        {
+            if from == to || !pack_names.contains(&to) {
+                continue;
+            }
+            edges
+                .entry((from, to))
+                .or_default()
+                .violations
+                .extend(counts);
+        }
+    }
+
+    let mut sorted_pack_names: Vec<&String> = pack_names.into_iter().collect();
+    sorted_pack_names.sort();
+
+    let mut graph = DiGraph::new();
+    let nodes: HashMap<&String, NodeIndex> = sorted_pack_names
+        .into_iter()
+        .map(|pack_name| (pack_name, graph.add_node(pack_name.clone())))
+        .collect();
+    for ((from, to), edge) in edges {
+        graph.add_edge(nodes[&from], nodes[&to], edge);
+    }
+
+    PackGraph { graph }
+}
+
+impl PackGraph {
+    // Keeps the packs within `depth` edges of the focused pack, following
+    // edges in either direction
+    fn neighborhood(self, pack_name: &str, depth: usize) -> PackGraph {
+        let graph = self.graph;
+        let Some(start) = graph.node_indices().find(|i| graph[*i] == pack_name)
+        else {
+            return PackGraph { graph };
+        };
+
+        let mut distances: HashMap<NodeIndex, usize> = HashMap::new();
+        distances.insert(start, 0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[&node];
+            if distance == depth {
+                continue;
+            }
+            for neighbor in graph.neighbors_undirected(node) {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        PackGraph {
+            graph: graph.filter_map(
+                |index, pack_name| {
+                    distances.contains_key(&index).then(|| pack_name.clone())
+                },
+                |_, edge| Some(edge.clone()),
+            ),
+        }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (&str, &str, &Edge)> {
+        self.graph.edge_indices().map(|index| {
+            let (from, to) = self.graph.edge_endpoints(index).unwrap();
+            (
+                self.graph[from].as_str(),
+                self.graph[to].as_str(),
+                &self.graph[index],
+            )
+        })
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph packs {\n");
+        for pack_name in self.graph.node_weights() {
+            dot.push_str(&format!("  \"{}\";\n", pack_name));
+        }
+        for (from, to, edge) in self.edges() {
+            let mut attributes = vec![];
+            if !edge.declared {
+                attributes.push(String::from("style=dashed"));
+            }
+            if !edge.violations.is_empty() {
+                attributes.push(format!("label=\"{}\"", edge.label()));
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\"{};\n",
+                from, to, attributes
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("graph LR\n");
+        for index in self.graph.node_indices() {
+            mermaid.push_str(&format!(
+                "  n{}[\"{}\"]\n",
+                index.index(),
+                self.graph[index]
+            ));
+        }
+        for index in self.graph.edge_indices() {
+            let (from, to) = self.graph.edge_endpoints(index).unwrap();
+            let edge = &self.graph[index];
+            let arrow = if edge.declared { "-->" } else { "-.->" };
+            let label = if edge.violations.is_empty() {
+                String::new()
+            } else {
+                format!("|{}|", edge.label())
+            };
+            mermaid.push_str(&format!(
+                "  n{} {}{} n{}\n",
+                from.index(),
+                arrow,
+                label,
+                to.index()
+            ));
+        }
+        mermaid
+    }
+
+    fn to_json(&self) -> anyhow::Result<String> {
+        let json_graph = JsonGraph {
+            nodes: self
+                .graph
+                .node_weights()
+                .map(|pack_name| JsonNode { name: pack_name })
+                .collect(),
+            edges: self
+                .edges()
+                .map(|(from, to, edge)| JsonEdge { from, to, edge })
+                .collect(),
+        };
+        Ok(format!("{}\n", serde_json::to_string_pretty(&json_graph)?))
+    }
+}
+
+impl Edge {
+    fn label(&self) -> String {
+        self.violations
+            .iter()
+            .map(|(violation_type, count)| {
+                format!("{}: {}", violation_type, count)
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::packs::configuration;
+
+    fn configuration_for(fixture: &str) -> Configuration {
+        configuration::get(
+            PathBuf::from("tests/fixtures")
+                .join(fixture)
+                .canonicalize()
+                .expect("Could not canonicalize path")
+                .as_path(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_declared_dependencies_to_dot() {
+        let configuration = configuration_for("simple_app");
+        let dot = build_pack_graph(&configuration, false).to_dot();
+        assert_eq!(
+            dot,
+            "\
+digraph packs {
+  \".\";
+  \"packs/bar\";
+  \"packs/baz\";
+  \"packs/foo\";
+  \"packs/foo\" -> \"packs/baz\";
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_implicit_dependencies_to_mermaid() {
+        let configuration = configuration_for("contains_package_todo");
+        let mermaid = build_pack_graph(&configuration, true).to_mermaid();
+        assert_eq!(
+            mermaid,
+            "\
+graph LR
+  n0[\".\"]
+  n1[\"packs/bar\"]
+  n2[\"packs/foo\"]
+  n2 -.->|dependency: 1| n1
+"
+        );
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let configuration = configuration_for("simple_app");
+        let pack_graph = build_pack_graph(&configuration, false)
+            .neighborhood("packs/baz", 1);
+        let pack_names: Vec<&String> =
+            pack_graph.graph.node_weights().collect();
+        assert_eq!(pack_names, vec!["packs/baz", "packs/foo"]);
+
+        let pack_graph = build_pack_graph(&configuration, false)
+            .neighborhood("packs/baz", 0);
+        assert_eq!(pack_graph.graph.node_count(), 1);
+        assert_eq!(pack_graph.graph.edge_count(), 0);
+    }
+}
@@ -0,0 +1,395 @@
+// A language server that reports violations as diagnostics while files are
+// being edited. It speaks LSP over stdio, so nothing else may print to stdout.
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    DidSaveTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, ExecuteCommand, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Command, Diagnostic, DiagnosticSeverity,
+    ExecuteCommandOptions, ExecuteCommandParams, NumberOrString, Position,
+    PublishDiagnosticsParams, Range, SaveOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::checker::pack_checker::ViolationType;
+use super::checker::{
+    build_strict_violation_message, check_references, get_checkers,
+    CheckerInterface,
+};
+use super::configuration::CONFIGURATION_FILE_NAMES;
+use super::constant_resolver::ConstantResolver;
+use super::pack::write_pack_to_disk;
+use super::parsing::process_file_contents;
+use super::reference_extractor::{
+    get_constant_resolver, get_references_for_processed_file,
+};
+use super::{Configuration, Violation};
+
+const ADD_DEPENDENCY_COMMAND: &str = "packs.addDependency";
+
+// Attached to each diagnostic so code actions know what it is about
+#[derive(Serialize, Deserialize)]
+struct DiagnosticData {
+    violation_type: String,
+    referencing_pack_name: String,
+    defining_pack_name: String,
+}
+
+struct Server {
+    connection: Connection,
+    configuration: Configuration,
+    constant_resolver: Box<dyn ConstantResolver + Send + Sync>,
+    checkers: Vec<Box<dyn CheckerInterface + Send + Sync>>,
+    // Contents of open documents, which may not be saved yet
+    documents: HashMap<Url, String>,
+}
+
+pub(crate) fn run(configuration: Configuration) -> anyhow::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(
+                    SaveOptions {
+                        include_text: Some(false),
+                    },
+                )),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        execute_command_provider: Some(ExecuteCommandOptions {
+            commands: vec![ADD_DEPENDENCY_COMMAND.to_owned()],
+            ..ExecuteCommandOptions::default()
+        }),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+    debug!("Language server initialized");
+
+    let configuration = Configuration {
+        // stdout is reserved for the protocol
+        print_files: false,
+        ..configuration
+    };
+    let mut server = Server {
+        constant_resolver: get_constant_resolver(&configuration)?,
+        checkers: get_checkers(&configuration),
+        configuration,
+        connection,
+        documents: HashMap::new(),
+    };
+    server.main_loop()?;
+
+    let Server { connection, .. } = server;
+    drop(connection);
+    io_threads.join()?;
+    debug!("Language server shut down");
+    Ok(())
+}
+
+impl Server {
+    fn main_loop(&mut self) -> anyhow::Result<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => {
+                    self.handle_notification(notification)?;
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> anyhow::Result<()> {
+        let result = match request.method.as_str() {
+            CodeActionRequest::METHOD => serde_json::from_value(request.params)
+                .map_err(anyhow::Error::from)
+                .and_then(|params| {
+                    Ok(serde_json::to_value(self.code_actions(&params))?)
+                }),
+            ExecuteCommand::METHOD => serde_json::from_value(request.params)
+                .map_err(anyhow::Error::from)
+                .and_then(|params| self.execute_command(&params))
+                .map(|()| serde_json::Value::Null),
+            method => Err(anyhow::anyhow!("Unsupported request: {}", method)),
+        };
+        let response = match result {
+            Ok(result) => Response::new_ok(request.id, result),
+            Err(error) => Response::new_err(
+                request.id,
+                lsp_server::ErrorCode::RequestFailed as i32,
+                format!("{:#}", error),
+            ),
+        };
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> anyhow::Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents
+                    .insert(uri.clone(), params.text_document.text);
+                self.publish_diagnostics(&uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                // We only ask for full document syncs, so the last change
+                // contains the whole document
+                if let Some(change) = params.content_changes.into_iter().last()
+                {
+                    self.documents.insert(uri.clone(), change.text);
+                }
+                self.publish_diagnostics(&uri)
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: lsp_types::DidSaveTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if self.needs_reload(&params.text_document.uri) {
+                    if let Err(error) = self.reload() {
+                        // Keep the last good configuration until it's fixed
+                        debug!("Failed to reload configuration: {:?}", error);
+                    }
+                }
+                Ok(())
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.send_diagnostics(uri, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Definitions only need to be re-read when configuration changes, or
+    // when the experimental parser is used, since it reads definitions from
+    // the source files themselves
+    fn needs_reload(&self, uri: &Url) -> bool {
+        let Ok(path) = uri.to_file_path() else {
+            return false;
+        };
+        let is_configuration_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| CONFIGURATION_FILE_NAMES.contains(&name))
+            .unwrap_or(false);
+        is_configuration_file || self.configuration.experimental_parser
+    }
+
+    fn reload(&mut self) -> anyhow::Result<()> {
+        debug!("Reloading configuration");
+        self.configuration = self.configuration.reload()?;
+        self.constant_resolver = get_constant_resolver(&self.configuration)?;
+        self.checkers = get_checkers(&self.configuration);
+        let uris: Vec<Url> = self.documents.keys().cloned().collect();
+        for uri in uris {
+            self.publish_diagnostics(&uri)?;
+        }
+        Ok(())
+    }
+
+    fn publish_diagnostics(&self, uri: &Url) -> anyhow::Result<()> {
+        let Some(text) = self.documents.get(uri) else {
+            return Ok(());
+        };
+        let Ok(path) = uri.to_file_path() else {
+            return Ok(());
+        };
+        let diagnostics = match self.check_document(&path, text) {
+            Ok(violations) => violations
+                .iter()
+                .map(|violation| diagnostic(violation, text))
+                .collect(),
+            Err(error) => {
+                // A broken configuration shouldn't take the server down
+                debug!("Failed to check {}: {:?}", path.display(), error);
+                vec![]
+            }
+        };
+        self.send_diagnostics(uri.clone(), diagnostics)
+    }
+
+    fn check_document(
+        &self,
+        path: &Path,
+        text: &str,
+    ) -> anyhow::Result<Vec<Violation>> {
+        if !self.configuration.included_files.contains(path) {
+            return Ok(vec![]);
+        }
+        let processed_file =
+            process_file_contents(path, text.to_owned(), &self.configuration);
+        let references = get_references_for_processed_file(
+            &self.configuration,
+            self.constant_resolver.as_ref(),
+            &processed_file,
+        )?;
+        let violations =
+            check_references(&self.configuration, &references, &self.checkers)?;
+        let pack_set = &self.configuration.pack_set;
+        Ok(violations
+            .into_iter()
+            .filter(|violation| {
+                self.configuration.ignore_recorded_violations
+                    || violation.identifier.strict
+                    || !pack_set.is_recorded(&violation.identifier)
+            })
+            .collect())
+    }
+
+    fn code_actions(
+        &self,
+        params: &CodeActionParams,
+    ) -> Vec<CodeActionOrCommand> {
+        let dependency: &str = ViolationType::Dependency.into();
+        params
+            .context
+            .diagnostics
+            .iter()
+            .filter_map(|diagnostic| {
+                let data: DiagnosticData =
+                    serde_json::from_value(diagnostic.data.clone()?).ok()?;
+                if data.violation_type != dependency {
+                    return None;
+                }
+                let title = format!(
+                    "Add `{}` to the dependencies of `{}`",
+                    data.defining_pack_name, data.referencing_pack_name
+                );
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: title.clone(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    command: Some(Command {
+                        title,
+                        command: ADD_DEPENDENCY_COMMAND.to_owned(),
+                        arguments: Some(vec![
+                            serde_json::Value::from(data.referencing_pack_name),
+                            serde_json::Value::from(data.defining_pack_name),
+                        ]),
+                    }),
+                    ..CodeAction::default()
+                }))
+            })
+            .collect()
+    }
+
+    fn execute_command(
+        &mut self,
+        params: &ExecuteCommandParams,
+    ) -> anyhow::Result<()> {
+        if params.command != ADD_DEPENDENCY_COMMAND {
+            bail!("Unknown command: {}", params.command);
+        }
+        let [from, to] = params.arguments.as_slice() else {
+            bail!("{} expects two pack names", ADD_DEPENDENCY_COMMAND);
+        };
+        let (Some(from), Some(to)) = (from.as_str(), to.as_str()) else {
+            bail!("{} expects two pack names", ADD_DEPENDENCY_COMMAND);
+        };
+
+        let pack_set = &self.configuration.pack_set;
+        let from_pack = pack_set
+            .for_pack(from)
+            .context(format!("`{}` not found", from))?;
+        let to_pack = pack_set
+            .for_pack(to)
+            .context(format!("`{}` not found", to))?;
+        if !from_pack.depends_on(&to_pack.name) {
+            write_pack_to_disk(&from_pack.add_dependency(to_pack))?;
+        }
+        self.reload()
+    }
+
+    fn send_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<Diagnostic>,
+    ) -> anyhow::Result<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        self.connection.sender.send(Message::Notification(
+            Notification::new(PublishDiagnostics::METHOD.to_owned(), params),
+        ))?;
+        Ok(())
+    }
+}
+
+fn diagnostic(violation: &Violation, text: &str) -> Diagnostic {
+    let identifier = &violation.identifier;
+    let message = if identifier.strict {
+        build_strict_violation_message(identifier)
+    } else {
+        violation.plain_message()
+    };
+    Diagnostic {
+        range: violation_range(violation, text),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(identifier.violation_type.clone())),
+        source: Some(String::from("packs")),
+        message,
+        data: serde_json::to_value(DiagnosticData {
+            violation_type: identifier.violation_type.clone(),
+            referencing_pack_name: identifier.referencing_pack_name.clone(),
+            defining_pack_name: identifier.defining_pack_name.clone(),
+        })
+        .ok(),
+        ..Diagnostic::default()
+    }
+}
+
+// Violations only know where the reference starts, so the range extends to
+// the end of the constant as written in the buffer
+fn violation_range(violation: &Violation, text: &str) -> Range {
+    let line = violation.source_location.line.saturating_sub(1);
+    let column = violation.source_location.column;
+    let constant_length = text
+        .lines()
+        .nth(line)
+        .map(|line_text| {
+            line_text
+                .chars()
+                .skip(column)
+                .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == ':')
+                .count()
+        })
+        .unwrap_or(0);
+    Range {
+        start: Position::new(line as u32, column as u32),
+        end: Position::new(line as u32, (column + constant_length) as u32),
+    }
+}
@@ -0,0 +1,323 @@
+// Coupling metrics per pack, after Robert C. Martin's package metrics:
+// afferent coupling (Ca), efferent coupling (Ce), instability
+// I = Ce / (Ca + Ce), abstractness A and distance from the main sequence
+// D = |A + I - 1|. Packs don't have abstract types, so abstractness is
+// estimated by the share of the pack's constants that are public.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use super::constant_resolver::ConstantDefinition;
+use super::reference_extractor::{get_all_references, get_constant_resolver};
+use super::Configuration;
+
+#[derive(Debug, PartialEq, Serialize)]
+struct PackMetrics {
+    pack: String,
+    // Packs that depend on or reference this pack
+    afferent_coupling: usize,
+    // Packs this pack depends on or references
+    efferent_coupling: usize,
+    // Unset for packs without any coupling
+    instability: Option<f64>,
+    // Unset for packs without constants
+    abstractness: Option<f64>,
+    distance_from_main_sequence: Option<f64>,
+    public_constants: usize,
+    constants: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MetricsFormat {
+    /// Comma-separated values
+    #[default]
+    Csv,
+    Json,
+}
+
+// (from pack, to pack)
+type Edges = BTreeSet<(String, String)>;
+
+pub(crate) fn metrics(
+    configuration: &Configuration,
+    format: MetricsFormat,
+) -> anyhow::Result<String> {
+    let mut edges = declared_edges(configuration);
+    for reference in
+        get_all_references(configuration, &configuration.included_files)?
+    {
+        if let Some(defining_pack_name) = reference.defining_pack_name {
+            if defining_pack_name != reference.referencing_pack_name {
+                edges.insert((
+                    reference.referencing_pack_name,
+                    defining_pack_name,
+                ));
+            }
+        }
+    }
+    let constant_resolver = get_constant_resolver(configuration)?;
+    let pack_metrics = compute_metrics(
+        configuration,
+        &edges,
+        constant_resolver
+            .fully_qualified_constant_name_to_constant_definition_map(),
+    );
+
+    match format {
+        MetricsFormat::Csv => Ok(to_csv(&pack_metrics)),
+        MetricsFormat::Json => Ok(serde_json::to_string_pretty(&pack_metrics)?),
+    }
+}
+
+fn declared_edges(configuration: &Configuration) -> Edges {
+    let pack_set = &configuration.pack_set;
+    let mut edges = Edges::new();
+    for pack in &pack_set.packs {
+        for dependency in &pack.dependencies {
+            for to_pack in pack_set.matching_packs(dependency) {
+                if to_pack.name != pack.name {
+                    edges.insert((pack.name.clone(), to_pack.name.clone()));
+                }
+            }
+        }
+    }
+    edges
+}
+
+fn compute_metrics(
+    configuration: &Configuration,
+    edges: &Edges,
+    definitions: &HashMap<String, Vec<ConstantDefinition>>,
+) -> Vec<PackMetrics> {
+    let mut afferent_couplings: HashMap<&str, usize> = HashMap::new();
+    let mut efferent_couplings: HashMap<&str, usize> = HashMap::new();
+    for (from, to) in edges {
+        *efferent_couplings.entry(from).or_default() += 1;
+        *afferent_couplings.entry(to).or_default() += 1;
+    }
+
+    // Pack name => (public constants, constants)
+    let mut constant_counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for (constant_name, constant_definitions) in definitions {
+        // Constants can be defined in several files, even in several packs
+        let mut public_by_pack: BTreeMap<&str, bool> = BTreeMap::new();
+        for definition in constant_definitions {
+            let Ok(Some(pack)) = configuration
+                .pack_set
+                .for_file(&definition.absolute_path_of_definition)
+            else {
+                continue;
+            };
+            let relative_file = definition
+                .absolute_path_of_definition
+                .strip_prefix(&configuration.absolute_root)
+                .unwrap_or(&definition.absolute_path_of_definition)
+                .to_string_lossy();
+            let is_public = pack.is_public_file(&relative_file)
+                || pack.declares_public_constant(constant_name);
+            *public_by_pack.entry(&pack.name).or_default() |= is_public;
+        }
+        for (pack_name, is_public) in public_by_pack {
+            let counts = constant_counts.entry(pack_name).or_default();
+            counts.0 += usize::from(is_public);
+            counts.1 += 1;
+        }
+    }
+
+    let mut pack_names: Vec<&String> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .map(|pack| &pack.name)
+        .collect();
+    pack_names.sort();
+    pack_names
+        .into_iter()
+        .map(|pack_name| {
+            let afferent_coupling = afferent_couplings
+                .get(pack_name.as_str())
+                .copied()
+                .unwrap_or_default();
+            let efferent_coupling = efferent_couplings
+                .get(pack_name.as_str())
+                .copied()
+                .unwrap_or_default();
+            let (public_constants, constants) = constant_counts
+                .get(pack_name.as_str())
+                .copied()
+                .unwrap_or_default();
+            let instability =
+                ratio(efferent_coupling, afferent_coupling + efferent_coupling);
+            let abstractness = ratio(public_constants, constants);
+            // From the exact ratios, rounding only what's printed
+            let distance_from_main_sequence = match (abstractness, instability)
+            {
+                (Some(abstractness), Some(instability)) => {
+                    Some((abstractness + instability - 1.0).abs())
+                }
+                _ => None,
+            };
+            PackMetrics {
+                pack: pack_name.clone(),
+                afferent_coupling,
+                efferent_coupling,
+                instability: instability.map(round),
+                abstractness: abstractness.map(round),
+                distance_from_main_sequence: distance_from_main_sequence
+                    .map(round),
+                public_constants,
+                constants,
+            }
+        })
+        .collect()
+}
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    if denominator == 0 {
+        None
+    } else {
+        Some(numerator as f64 / denominator as f64)
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+fn to_csv(pack_metrics: &[PackMetrics]) -> String {
+    let mut lines = vec![String::from(
+        "pack,afferent_coupling,efferent_coupling,instability,abstractness,distance_from_main_sequence,public_constants,constants",
+    )];
+    let optional = |value: Option<f64>| {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    };
+    for metrics in pack_metrics {
+        lines.push(format!(
+            "{},{},{},{},{},{},{},{}",
+            csv_field(&metrics.pack),
+            metrics.afferent_coupling,
+            metrics.efferent_coupling,
+            optional(metrics.instability),
+            optional(metrics.abstractness),
+            optional(metrics.distance_from_main_sequence),
+            metrics.public_constants,
+            metrics.constants,
+        ));
+    }
+    lines.join("\n")
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    use crate::packs::pack::Pack;
+    use crate::packs::PackSet;
+
+    use super::*;
+
+    fn definition(name: &str, file: &str) -> (String, Vec<ConstantDefinition>) {
+        (
+            name.to_owned(),
+            vec![ConstantDefinition {
+                fully_qualified_name: name.to_owned(),
+                absolute_path_of_definition: PathBuf::from("/app").join(file),
+            }],
+        )
+    }
+
+    #[test]
+    fn test_compute_metrics() {
+        let packs = ["packs/foo", "packs/bar", "packs/baz"].map(|name| Pack {
+            name: name.to_owned(),
+            yml: PathBuf::from("/app").join(name).join("package.yml"),
+            relative_path: PathBuf::from(name),
+            ..Pack::default()
+        });
+        let files = [
+            "packs/bar/app/public/bar.rb",
+            "packs/bar/app/services/internal.rb",
+            "packs/bar/app/services/other.rb",
+            "packs/baz/app/services/baz.rb",
+        ];
+        let owning_package_yml_for_file = files
+            .iter()
+            .map(|file| {
+                let pack_name = file.split('/').take(2).collect::<Vec<_>>();
+                (
+                    PathBuf::from("/app").join(file),
+                    PathBuf::from("/app")
+                        .join(pack_name.join("/"))
+                        .join("package.yml"),
+                )
+            })
+            .collect();
+        let root_pack = Pack {
+            name: String::from("."),
+            yml: PathBuf::from("/app/package.yml"),
+            ..Pack::default()
+        };
+        let configuration = Configuration {
+            absolute_root: PathBuf::from("/app"),
+            pack_set: PackSet::build(
+                HashSet::from_iter(packs.into_iter().chain([root_pack])),
+                owning_package_yml_for_file,
+            )
+            .unwrap(),
+            ..Configuration::default()
+        };
+        let edges = Edges::from([
+            (String::from("packs/foo"), String::from("packs/bar")),
+            (String::from("packs/baz"), String::from("packs/bar")),
+            (String::from("packs/bar"), String::from("packs/baz")),
+        ]);
+        let definitions = HashMap::from([
+            definition("::Bar", files[0]),
+            definition("::Bar::Internal", files[1]),
+            definition("::Bar::Other", files[2]),
+            definition("::Baz", files[3]),
+        ]);
+
+        let pack_metrics =
+            compute_metrics(&configuration, &edges, &definitions);
+
+        assert_eq!(
+            pack_metrics[1],
+            PackMetrics {
+                pack: String::from("packs/bar"),
+                afferent_coupling: 2,
+                efferent_coupling: 1,
+                instability: Some(0.3333),
+                abstractness: Some(0.3333),
+                // Not 0.3334, from the rounded ratios
+                distance_from_main_sequence: Some(0.3333),
+                public_constants: 1,
+                constants: 3,
+            }
+        );
+        assert_eq!(
+            to_csv(&pack_metrics),
+            "\
+pack,afferent_coupling,efferent_coupling,instability,abstractness,distance_from_main_sequence,public_constants,constants
+.,0,0,,,,0,0
+packs/bar,2,1,0.3333,0.3333,0.3333,1,3
+packs/baz,1,1,0.5,0,0.5,0,1
+packs/foo,0,1,1,,,0,0"
+        );
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("packs/foo"), "packs/foo");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs::File,
     hash::Hasher,
     io::Read,
@@ -7,12 +7,14 @@ use std::{
 };
 
 use anyhow::Context;
+use chrono::NaiveDate;
 use core::hash::Hash;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_yaml::Value;
 
 use super::{
-    checker::ViolationIdentifier, file_utils::expand_glob, ignored, PackageTodo,
+    checker::ViolationIdentifier, file_utils::expand_glob, ignored,
+    package_todo::ViolationGroup, PackageTodo,
 };
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
@@ -58,6 +60,16 @@ pub struct Pack {
     )]
     pub enforce_layers: Option<CheckerSetting>,
 
+    // Fails `validate` when the pack is in a cycle of packs referencing each
+    // other's constants, even if their `dependencies` don't form one
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_checker_setting",
+        deserialize_with = "deserialize_checker_setting"
+    )]
+    pub enforce_acyclic_references: Option<CheckerSetting>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub owner: Option<String>,
 
@@ -78,6 +90,15 @@ pub struct Pack {
     )]
     pub ignored_dependencies: HashSet<String>,
 
+    // Packs that only the pack's test files (see `test_paths` in
+    // `packwerk.yml`) may depend on
+    #[serde(
+        default,
+        skip_serializing_if = "HashSet::is_empty",
+        serialize_with = "serialize_sorted_hashset_of_strings"
+    )]
+    pub dev_dependencies: HashSet<String>,
+
     #[serde(
         default,
         skip_serializing_if = "HashSet::is_empty",
@@ -92,6 +113,21 @@ pub struct Pack {
     )]
     pub private_constants: HashSet<String>,
 
+    // Constants other packs may reference wherever they're defined, like the
+    // ones in the public folder. `::Namespace::*` makes every constant in
+    // the namespace public.
+    #[serde(
+        default,
+        skip_serializing_if = "HashSet::is_empty",
+        serialize_with = "serialize_sorted_hashset_of_strings"
+    )]
+    pub public_constants: HashSet<String>,
+
+    // The most violations the pack's package_todo.yml may record, in total
+    // or per violation type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_todo_violations: Option<MaxTodoViolations>,
+
     #[serde(skip)]
     pub package_todo: PackageTodo,
 
@@ -121,6 +157,11 @@ pub struct Pack {
     #[serde(skip_serializing_if = "is_default_public_folder")]
     pub public_folder: Option<PathBuf>,
 
+    // Folders, files or globs relative to the pack whose constants other
+    // packs may reference. Replaces the default `app/public` folder.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub public_paths: Vec<String>,
+
     #[serde(flatten)]
     pub client_keys: HashMap<String, Value>,
 
@@ -154,6 +195,13 @@ pub struct EnforcementGlobsIgnore {
     pub reason: String,
 }
 
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum MaxTodoViolations {
+    Total(usize),
+    PerViolationType(BTreeMap<String, usize>),
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize, Clone)]
 pub enum CheckerSetting {
     #[default]
@@ -179,6 +227,31 @@ impl Pack {
 
     pub fn all_violations(&self) -> Vec<ViolationIdentifier> {
         let mut violations = Vec::new();
+        self.for_each_violation(|identifier, _| violations.push(identifier));
+        violations
+    }
+
+    // Recorded violations whose package_todo.yml entry has expired, with the
+    // date it expired on
+    pub(crate) fn expired_violations(
+        &self,
+        today: NaiveDate,
+    ) -> Vec<(ViolationIdentifier, NaiveDate)> {
+        let mut violations = Vec::new();
+        self.for_each_violation(|identifier, violation_group| {
+            if let Some(expires) = violation_group.expires {
+                if violation_group.is_expired(today) {
+                    violations.push((identifier, expires));
+                }
+            }
+        });
+        violations
+    }
+
+    fn for_each_violation(
+        &self,
+        mut f: impl FnMut(ViolationIdentifier, &ViolationGroup),
+    ) {
         let violations_by_pack = &self.package_todo.violations_by_defining_pack;
         for (defining_pack_name, violation_groups) in violations_by_pack {
             for (constant_name, violation_group) in violation_groups {
@@ -193,12 +266,11 @@ impl Pack {
                             defining_pack_name: defining_pack_name.clone(),
                         };
 
-                        violations.push(identifier);
+                        f(identifier, violation_group);
                     }
                 }
             }
         }
-        violations
     }
 
     pub fn from_path(
@@ -336,12 +408,88 @@ impl Pack {
         }
     }
 
+    // Whether constants defined in the file, relative to the project root,
+    // are public: it's in `public_folder` or matches one of `public_paths`
+    pub(crate) fn is_public_file(&self, relative_file: &str) -> bool {
+        if self.public_folder.is_some() || self.public_paths.is_empty() {
+            let public_folder = self.public_folder();
+            if relative_file
+                .starts_with(public_folder.to_string_lossy().as_ref())
+            {
+                return true;
+            }
+        }
+
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::default()
+        };
+        let relative_file = Path::new(relative_file);
+        self.public_paths.iter().any(|public_path| {
+            let public_path = if self.relative_path == Path::new(".") {
+                PathBuf::from(public_path)
+            } else {
+                self.relative_path.join(public_path)
+            };
+            // A glob may match the file or a folder containing it
+            relative_file.starts_with(&public_path)
+                || glob::Pattern::new(&public_path.to_string_lossy()).is_ok_and(
+                    |pattern| {
+                        relative_file.ancestors().any(|path| {
+                            pattern.matches_path_with(path, options)
+                        })
+                    },
+                )
+        })
+    }
+
+    pub(crate) fn depends_on(&self, pack_name: &str) -> bool {
+        names_pack(&self.dependencies, pack_name)
+    }
+
+    // The glob in `dependencies` that makes this pack depend on another, if
+    // it isn't listed by name
+    pub(crate) fn dependency_glob(&self, pack_name: &str) -> Option<&str> {
+        if !self.depends_on(pack_name) || self.dependencies.contains(pack_name)
+        {
+            return None;
+        }
+        self.dependencies
+            .iter()
+            .find(|entry| {
+                pack_name_pattern(entry).is_some_and(|pattern| {
+                    pack_name_pattern_matches(&pattern, pack_name)
+                })
+            })
+            .map(String::as_str)
+    }
+
+    pub(crate) fn dev_depends_on(&self, pack_name: &str) -> bool {
+        names_pack(&self.dev_dependencies, pack_name)
+    }
+
+    pub(crate) fn ignores_dependency_on(&self, pack_name: &str) -> bool {
+        names_pack(&self.ignored_dependencies, pack_name)
+    }
+
+    pub(crate) fn is_visible_to(&self, pack_name: &str) -> bool {
+        self.visible_to
+            .as_ref()
+            .is_some_and(|visible_to| names_pack(visible_to, pack_name))
+    }
+
     pub(crate) fn add_dependency(&self, to_pack: &Pack) -> Pack {
         let mut new_pack = self.clone();
         new_pack.dependencies.insert(to_pack.name.clone());
         new_pack
     }
 
+    pub(crate) fn declares_public_constant(&self, constant_name: &str) -> bool {
+        self.public_constants.iter().any(|public_constant| {
+            public_constant_matches(public_constant, constant_name)
+        })
+    }
+
     pub(crate) fn ignores_for_enforcement(
         &self,
         enforcement: &str,
@@ -366,6 +514,64 @@ impl Pack {
     }
 }
 
+// Entries of `dependencies`, `dev_dependencies`, `ignored_dependencies` and
+// `visible_to` can be globs matching pack names, e.g. `packs/platform/*`
+pub(crate) fn is_pack_name_glob(entry: &str) -> bool {
+    entry.contains(['*', '?', '['])
+}
+
+// `None` for entries that aren't globs, or aren't valid ones
+pub(crate) fn pack_name_pattern(entry: &str) -> Option<glob::Pattern> {
+    if is_pack_name_glob(entry) {
+        glob::Pattern::new(entry).ok()
+    } else {
+        None
+    }
+}
+
+pub(crate) fn pack_name_pattern_matches(
+    pattern: &glob::Pattern,
+    pack_name: &str,
+) -> bool {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..glob::MatchOptions::default()
+    };
+    pattern.matches_with(pack_name, options)
+}
+
+fn names_pack(entries: &HashSet<String>, pack_name: &str) -> bool {
+    entries.contains(pack_name)
+        || entries.iter().any(|entry| {
+            pack_name_pattern(entry).is_some_and(|pattern| {
+                pack_name_pattern_matches(&pattern, pack_name)
+            })
+        })
+}
+
+// Whether a `public_constants` entry makes the constant public
+pub(crate) fn public_constant_matches(
+    public_constant: &str,
+    constant_name: &str,
+) -> bool {
+    match public_constant.strip_suffix('*') {
+        Some(namespace) => {
+            is_valid_public_constant(public_constant)
+                && constant_name.starts_with(namespace)
+        }
+        None => public_constant == constant_name,
+    }
+}
+
+// A `public_constants` entry is a constant name or a namespace ending in
+// `::*`, so `::Billing::*` can't make `::BillingFoo` public
+pub(crate) fn is_valid_public_constant(public_constant: &str) -> bool {
+    match public_constant.strip_suffix("::*") {
+        Some(namespace) => !namespace.contains('*'),
+        None => !public_constant.contains('*'),
+    }
+}
+
 fn serialize_sorted_hashset_of_strings<S>(
     value: &HashSet<String>,
     serializer: S,
@@ -432,7 +638,7 @@ pub fn write_pack_to_disk(pack: &Pack) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn serialize_checker_setting<S>(
+pub(crate) fn serialize_checker_setting<S>(
     value: &Option<CheckerSetting>,
     serializer: S,
 ) -> Result<S::Ok, S::Error>
@@ -447,7 +653,7 @@ where
     }
 }
 
-fn deserialize_checker_setting<'de, D>(
+pub(crate) fn deserialize_checker_setting<'de, D>(
     deserializer: D,
 ) -> Result<Option<CheckerSetting>, D::Error>
 where
@@ -530,6 +736,113 @@ foobar: true
         assert_eq!(expected, actual)
     }
 
+    #[test]
+    fn test_serde_with_public_paths() {
+        let pack_yml = r#"
+public_paths:
+  - lib/
+  - app/public
+  - app/graphql/**/public
+"#;
+
+        let actual = reserialize_pack(pack_yml);
+
+        let expected = r#"
+public_paths:
+- lib/
+- app/public
+- app/graphql/**/public
+"#
+        .trim_start();
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn test_is_public_file() {
+        let pack = Pack {
+            relative_path: PathBuf::from("packs/foo"),
+            ..Pack::default()
+        };
+        assert!(pack.is_public_file("packs/foo/app/public/foo.rb"));
+        assert!(!pack.is_public_file("packs/foo/lib/foo.rb"));
+
+        let pack = Pack {
+            public_paths: vec![
+                String::from("lib/"),
+                String::from("app/graphql/**/public"),
+                String::from("app/models/*.rb"),
+            ],
+            ..pack
+        };
+        assert!(pack.is_public_file("packs/foo/lib/foo.rb"));
+        assert!(pack.is_public_file("packs/foo/app/graphql/types/public/a.rb"));
+        assert!(pack.is_public_file("packs/foo/app/models/a.rb"));
+        assert!(!pack.is_public_file("packs/foo/app/models/b/c.rb"));
+        assert!(!pack.is_public_file("packs/foo/app/graphql/types/a.rb"));
+        assert!(!pack.is_public_file("packs/foo/app/public/foo.rb"));
+        assert!(!pack.is_public_file("packs/foo/library/foo.rb"));
+
+        let pack = Pack {
+            public_folder: Some(PathBuf::from("packs/foo/app/api")),
+            ..pack
+        };
+        assert!(pack.is_public_file("packs/foo/app/api/foo.rb"));
+        assert!(pack.is_public_file("packs/foo/lib/foo.rb"));
+    }
+
+    #[test]
+    fn test_pack_name_globs() {
+        let pack = Pack {
+            dependencies: HashSet::from([
+                String::from("packs/platform/*"),
+                String::from("packs/shop"),
+            ]),
+            ignored_dependencies: HashSet::from([String::from(
+                "packs/legacy/**",
+            )]),
+            visible_to: Some(HashSet::from([String::from("packs/*/api")])),
+            ..Pack::default()
+        };
+        assert!(pack.depends_on("packs/shop"));
+        assert!(pack.depends_on("packs/platform/core"));
+        assert!(!pack.depends_on("packs/platform/core/nested"));
+        assert!(!pack.depends_on("packs/platforms"));
+        assert_eq!(
+            pack.dependency_glob("packs/platform/core"),
+            Some("packs/platform/*")
+        );
+        assert_eq!(pack.dependency_glob("packs/shop"), None);
+        assert_eq!(pack.dependency_glob("packs/platforms"), None);
+        assert!(pack.ignores_dependency_on("packs/legacy/billing/v1"));
+        assert!(pack.is_visible_to("packs/billing/api"));
+        assert!(!pack.is_visible_to("packs/billing"));
+        assert!(!Pack::default().is_visible_to("packs/billing"));
+    }
+
+    #[test]
+    fn test_public_constant_matches() {
+        assert!(public_constant_matches("::Billing", "::Billing"));
+        assert!(public_constant_matches(
+            "::Billing::*",
+            "::Billing::Invoice"
+        ));
+        assert!(public_constant_matches(
+            "::Billing::Api::*",
+            "::Billing::Api::V1::Client"
+        ));
+        // Constants whose names only start the same way are siblings
+        assert!(!public_constant_matches("::Billing::*", "::BillingFoo"));
+        assert!(!public_constant_matches("::Billing*", "::BillingFoo"));
+        assert!(!public_constant_matches(
+            "::Billing::Api*",
+            "::Billing::ApiInternal"
+        ));
+        assert!(!is_valid_public_constant("::Billing*"));
+        assert!(!is_valid_public_constant("::*::Invoice"));
+        assert!(is_valid_public_constant("::Billing::*"));
+    }
+
     #[test]
     fn test_serde_with_arbitrary_client_keys() {
         let pack_yml = r#"
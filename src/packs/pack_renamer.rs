@@ -0,0 +1,330 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use anyhow::{bail, Context};
+
+use super::checker::ViolationIdentifier;
+use super::pack::{serialize_pack, write_pack_to_disk, Pack};
+use super::package_todo::write_violation_identifiers_to_disk;
+use super::parsing::ruby::zeitwerk::{
+    automatic_namespace_for_pack, has_automatic_pack_namespace,
+};
+use super::Configuration;
+
+// Maps a pack name or a path relative to the root from the old pack directory
+// to the new one. Nested packs and files move along with the pack.
+struct PackRename<'a> {
+    old_name: &'a str,
+    new_name: &'a str,
+}
+
+impl PackRename<'_> {
+    fn rename(&self, name: &str) -> Option<String> {
+        if name == self.old_name {
+            return Some(self.new_name.to_owned());
+        }
+        name.strip_prefix(self.old_name)
+            .filter(|rest| rest.starts_with('/'))
+            .map(|rest| format!("{}{}", self.new_name, rest))
+    }
+
+    fn rename_or_keep(&self, name: &str) -> String {
+        self.rename(name).unwrap_or_else(|| name.to_owned())
+    }
+}
+
+// A constant namespace that changes because a pack with
+// `automatic_pack_namespace` now has a different last name
+struct NamespaceChange {
+    old_namespace: String,
+    new_namespace: String,
+}
+
+impl NamespaceChange {
+    fn rename(&self, constant_name: &str) -> Option<String> {
+        if constant_name == self.old_namespace {
+            return Some(self.new_namespace.clone());
+        }
+        constant_name
+            .strip_prefix(&self.old_namespace)
+            .filter(|rest| rest.starts_with("::"))
+            .map(|rest| format!("{}{}", self.new_namespace, rest))
+    }
+
+    fn rename_or_keep(&self, constant_name: &str) -> String {
+        self.rename(constant_name)
+            .unwrap_or_else(|| constant_name.to_owned())
+    }
+}
+
+pub(crate) fn rename_pack(
+    configuration: &Configuration,
+    old_name: String,
+    new_name: String,
+) -> anyhow::Result<()> {
+    let new_name = new_name.trim_end_matches('/').to_owned();
+    let pack = configuration
+        .pack_set
+        .for_pack(&old_name)
+        .context(format!("`{}` not found", old_name))?;
+    if pack.name == "." {
+        bail!("The root pack can't be renamed");
+    }
+    let pack_rename = PackRename {
+        old_name: &pack.name,
+        new_name: &new_name,
+    };
+    if pack_rename.rename(&new_name).is_some() && pack.name != new_name {
+        bail!("`{}` can't be moved inside of itself", pack.name);
+    }
+    let old_directory = configuration.absolute_root.join(&pack.name);
+    let new_directory = configuration.absolute_root.join(&new_name);
+    if new_directory.exists() {
+        bail!("`{}` already exists", new_name);
+    }
+
+    let namespace_changes = namespace_changes(configuration, &pack_rename);
+    let renamed_packs: Vec<Pack> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .map(|pack| {
+            renamed_pack(configuration, pack, &pack_rename, &namespace_changes)
+        })
+        .collect();
+    let identifiers =
+        renamed_identifiers(configuration, &pack_rename, &namespace_changes);
+
+    if let Some(parent) = new_directory.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&old_directory, &new_directory)
+        .context(format!("Failed to move {}", old_directory.display()))?;
+    println!("Renamed `{}` to `{}`", pack.name, new_name);
+
+    for (pack, renamed_pack) in
+        configuration.pack_set.packs.iter().zip(&renamed_packs)
+    {
+        if serialize_pack(pack) != serialize_pack(renamed_pack) {
+            write_pack_to_disk(renamed_pack)?;
+            println!("Updated {}", renamed_pack.relative_yml().display());
+        }
+    }
+
+    let new_configuration = configuration.reload()?;
+    if new_configuration.pack_set.for_pack(&new_name).is_err() {
+        println!(
+            "Warning: `{}` is not included by the package_paths in packwerk.yml",
+            new_name
+        );
+    }
+
+    // The header of a package_todo.yml file names its pack, so renamed packs
+    // get rewritten even if none of their violations changed
+    let mut todo_pack_names: HashSet<String> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .filter(|pack| {
+            !pack.package_todo.violations_by_defining_pack.is_empty()
+        })
+        .filter_map(|pack| pack_rename.rename(&pack.name))
+        .collect();
+    for (old_identifier, new_identifier) in configuration
+        .pack_set
+        .all_violations
+        .iter()
+        .zip(&identifiers)
+    {
+        if old_identifier != new_identifier {
+            todo_pack_names
+                .insert(new_identifier.referencing_pack_name.clone());
+        }
+    }
+    if !todo_pack_names.is_empty() {
+        write_violation_identifiers_to_disk(
+            &new_configuration,
+            &todo_pack_names,
+            identifiers,
+        );
+        let mut todo_pack_names: Vec<&String> =
+            todo_pack_names.iter().collect();
+        todo_pack_names.sort();
+        for pack_name in todo_pack_names {
+            println!("Updated package_todo.yml for `{}`", pack_name);
+        }
+    }
+
+    for (old_pack_name, namespace_change) in &namespace_changes {
+        println!(
+            "Constants in `{}` are now expected in `{}` instead of `{}` (automatic_pack_namespace), update the Ruby code to match",
+            pack_rename.rename_or_keep(old_pack_name),
+            namespace_change.new_namespace,
+            namespace_change.old_namespace
+        );
+    }
+
+    Ok(())
+}
+
+// Keyed by the old name of each renamed pack that uses an automatic namespace
+fn namespace_changes(
+    configuration: &Configuration,
+    pack_rename: &PackRename,
+) -> HashMap<String, NamespaceChange> {
+    configuration
+        .pack_set
+        .packs
+        .iter()
+        .filter(|pack| has_automatic_pack_namespace(pack))
+        .filter_map(|pack| {
+            let new_pack_name = pack_rename.rename(&pack.name)?;
+            let new_pack = Pack {
+                name: new_pack_name,
+                ..pack.clone()
+            };
+            let old_namespace = automatic_namespace_for_pack(pack);
+            let new_namespace = automatic_namespace_for_pack(&new_pack);
+            if old_namespace == new_namespace {
+                return None;
+            }
+            Some((
+                pack.name.clone(),
+                NamespaceChange {
+                    old_namespace,
+                    new_namespace,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn renamed_pack(
+    configuration: &Configuration,
+    pack: &Pack,
+    pack_rename: &PackRename,
+    namespace_changes: &HashMap<String, NamespaceChange>,
+) -> Pack {
+    let mut renamed_pack = pack.clone();
+    if let Some(new_name) = pack_rename.rename(&pack.name) {
+        renamed_pack.yml = configuration
+            .absolute_root
+            .join(&new_name)
+            .join("package.yml");
+        renamed_pack.relative_path = new_name.clone().into();
+        renamed_pack.name = new_name;
+    }
+
+    renamed_pack.dependencies = pack
+        .dependencies
+        .iter()
+        .map(|name| pack_rename.rename_or_keep(name))
+        .collect();
+    renamed_pack.dev_dependencies = pack
+        .dev_dependencies
+        .iter()
+        .map(|name| pack_rename.rename_or_keep(name))
+        .collect();
+    renamed_pack.ignored_dependencies = pack
+        .ignored_dependencies
+        .iter()
+        .map(|name| pack_rename.rename_or_keep(name))
+        .collect();
+    renamed_pack.visible_to = pack.visible_to.as_ref().map(|visible_to| {
+        visible_to
+            .iter()
+            .map(|name| pack_rename.rename_or_keep(name))
+            .collect()
+    });
+    renamed_pack.public_folder = pack.public_folder.as_ref().map(|folder| {
+        pack_rename.rename_or_keep(&folder.to_string_lossy()).into()
+    });
+
+    if let Some(namespace_change) = namespace_changes.get(&pack.name) {
+        renamed_pack.private_constants = pack
+            .private_constants
+            .iter()
+            .map(|constant| namespace_change.rename_or_keep(constant))
+            .collect();
+        renamed_pack.public_constants = pack
+            .public_constants
+            .iter()
+            .map(|constant| namespace_change.rename_or_keep(constant))
+            .collect();
+        renamed_pack.ignored_private_constants = pack
+            .ignored_private_constants
+            .iter()
+            .map(|constant| namespace_change.rename_or_keep(constant))
+            .collect();
+    }
+
+    renamed_pack
+}
+
+fn renamed_identifiers(
+    configuration: &Configuration,
+    pack_rename: &PackRename,
+    namespace_changes: &HashMap<String, NamespaceChange>,
+) -> Vec<ViolationIdentifier> {
+    configuration
+        .pack_set
+        .all_violations
+        .iter()
+        .map(|identifier| {
+            let constant_name =
+                match namespace_changes.get(&identifier.defining_pack_name) {
+                    Some(namespace_change) => namespace_change
+                        .rename_or_keep(&identifier.constant_name),
+                    None => identifier.constant_name.clone(),
+                };
+            ViolationIdentifier {
+                file: pack_rename.rename_or_keep(&identifier.file),
+                constant_name,
+                referencing_pack_name: pack_rename
+                    .rename_or_keep(&identifier.referencing_pack_name),
+                defining_pack_name: pack_rename
+                    .rename_or_keep(&identifier.defining_pack_name),
+                ..identifier.clone()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_rename() {
+        let pack_rename = PackRename {
+            old_name: "packs/foo",
+            new_name: "packs/bar",
+        };
+        assert_eq!(pack_rename.rename("packs/foo"), Some("packs/bar".into()));
+        assert_eq!(
+            pack_rename.rename("packs/foo/nested"),
+            Some("packs/bar/nested".into())
+        );
+        assert_eq!(
+            pack_rename.rename("packs/foo/app/services/foo.rb"),
+            Some("packs/bar/app/services/foo.rb".into())
+        );
+        assert_eq!(pack_rename.rename("packs/foobar"), None);
+        assert_eq!(pack_rename.rename("packs/baz"), None);
+    }
+
+    #[test]
+    fn test_namespace_change() {
+        let namespace_change = NamespaceChange {
+            old_namespace: "::Foo".into(),
+            new_namespace: "::Bar".into(),
+        };
+        assert_eq!(namespace_change.rename("::Foo"), Some("::Bar".into()));
+        assert_eq!(
+            namespace_change.rename("::Foo::Widget"),
+            Some("::Bar::Widget".into())
+        );
+        assert_eq!(namespace_change.rename("::FooBar"), None);
+        assert_eq!(namespace_change.rename("::Baz::Foo"), None);
+    }
+}
@@ -4,9 +4,16 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use chrono::NaiveDate;
 use itertools::Itertools;
 
-use super::{checker::ViolationIdentifier, pack::Pack, Configuration};
+use super::{
+    checker::ViolationIdentifier,
+    pack::{
+        is_pack_name_glob, pack_name_pattern, pack_name_pattern_matches, Pack,
+    },
+    package_todo, Configuration,
+};
 
 #[derive(Default, Debug)]
 pub struct PackSet {
@@ -20,6 +27,9 @@ pub struct PackSet {
     // We will also likely want to have an optimization that only rewrites the files
     // that have different violations.
     pub all_violations: HashSet<ViolationIdentifier>,
+    // Recorded violations whose package_todo.yml entry has expired, which
+    // `check` reports as if they weren't recorded
+    pub expired_violations: HashMap<ViolationIdentifier, NaiveDate>,
 }
 
 #[derive(Debug)]
@@ -44,13 +54,16 @@ impl PackSet {
         let mut indexed_packs_by_name: HashMap<String, Pack> = HashMap::new();
         let mut indexed_packs_by_yml: HashMap<PathBuf, String> = HashMap::new();
 
+        let today = package_todo::today();
         let mut all_violations = HashSet::new();
+        let mut expired_violations = HashMap::new();
         for pack in &packs {
             indexed_packs_by_name.insert(pack.name.clone(), pack.clone());
             indexed_packs_by_yml.insert(pack.yml.clone(), pack.name.clone());
             for violation_identifier in pack.all_violations() {
                 all_violations.insert(violation_identifier);
             }
+            expired_violations.extend(pack.expired_violations(today));
         }
 
         let mut owning_pack_name_for_file: HashMap<PathBuf, String> =
@@ -72,10 +85,17 @@ impl PackSet {
             indexed_packs,
             packs,
             all_violations,
+            expired_violations,
             owning_pack_name_for_file,
         })
     }
 
+    // Whether a violation is excused by an unexpired package_todo.yml entry
+    pub(crate) fn is_recorded(&self, identifier: &ViolationIdentifier) -> bool {
+        self.all_violations.contains(identifier)
+            && !self.expired_violations.contains_key(identifier)
+    }
+
     pub fn for_file(
         &self,
         absolute_file_path: &Path,
@@ -104,6 +124,19 @@ impl PackSet {
         }
     }
 
+    // The packs named by an entry of `dependencies`, `ignored_dependencies`
+    // or `visible_to`, which may be a glob
+    pub(crate) fn matching_packs(&self, entry: &str) -> Vec<&Pack> {
+        match pack_name_pattern(entry) {
+            Some(pattern) => self
+                .packs
+                .iter()
+                .filter(|pack| pack_name_pattern_matches(&pattern, &pack.name))
+                .collect(),
+            None => self.for_pack(entry).ok().into_iter().collect(),
+        }
+    }
+
     // Returns all of the package dependencies in the pack set.
     pub fn all_pack_dependencies<'a>(
         &'a self,
@@ -112,6 +145,20 @@ impl PackSet {
         let mut pack_refs: Vec<PackDependency> = Vec::new();
         for from_pack in &configuration.pack_set.packs {
             for dependency_pack_name in &from_pack.dependencies {
+                // A pack matching its own glob doesn't depend on itself, and
+                // globs matching no pack are reported by `validate`
+                if is_pack_name_glob(dependency_pack_name) {
+                    pack_refs.extend(
+                        self.matching_packs(dependency_pack_name)
+                            .into_iter()
+                            .filter(|to_pack| to_pack.name != from_pack.name)
+                            .map(|to_pack| PackDependency {
+                                from_pack,
+                                to_pack,
+                            }),
+                    );
+                    continue;
+                }
                 match configuration.pack_set.for_pack(dependency_pack_name) {
                     Ok(to_pack) => {
                         pack_refs.push(PackDependency { from_pack, to_pack })
@@ -164,4 +211,20 @@ mod tests {
         let actual_pack = pack_set.for_pack("packs/foo/");
         assert!(actual_pack.is_ok());
     }
+
+    #[test]
+    fn matching_packs_with_glob() {
+        let pack_set = example_pack_set();
+        let names = |entry: &str| -> Vec<String> {
+            pack_set
+                .matching_packs(entry)
+                .iter()
+                .map(|pack| pack.name.clone())
+                .collect()
+        };
+        assert_eq!(names("packs/*"), vec!["packs/foo"]);
+        assert_eq!(names("packs/foo"), vec!["packs/foo"]);
+        assert!(names("packs/*/bar").is_empty());
+        assert!(names("packs/bar").is_empty());
+    }
 }
@@ -1,9 +1,12 @@
+use chrono::NaiveDate;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use tracing::debug;
 
-use super::{pack::Pack, Configuration, Violation};
+use super::{
+    checker::ViolationIdentifier, pack::Pack, Configuration, Violation,
+};
 
 #[derive(PartialEq, Debug, Eq, Deserialize, Serialize, Default, Clone)]
 pub struct ViolationGroup {
@@ -12,6 +15,26 @@ pub struct ViolationGroup {
     pub violation_types: HashSet<String>,
     #[serde(serialize_with = "serialize_sorted_set")]
     pub files: HashSet<String>,
+    // Annotations written by hand, kept across `update` runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+}
+
+impl ViolationGroup {
+    // From its `expires` date on, an entry no longer excuses its violations
+    pub(crate) fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires <= today)
+    }
+
+    fn keep_annotations(&mut self, previous: &ViolationGroup) {
+        self.expires = previous.expires;
+        self.owner = previous.owner.clone();
+        self.ticket = previous.ticket.clone();
+    }
 }
 
 fn serialize_sorted_set<S>(
@@ -33,6 +56,69 @@ pub struct PackageTodo {
         BTreeMap<String, BTreeMap<String, ViolationGroup>>,
 }
 
+impl PackageTodo {
+    // One violation per violation type and file of each entry, like
+    // `Pack::all_violations`
+    pub(crate) fn violation_count(&self) -> usize {
+        self.violations_by_defining_pack
+            .values()
+            .flat_map(|violation_groups| violation_groups.values())
+            .map(|violation_group| {
+                violation_group.violation_types.len()
+                    * violation_group.files.len()
+            })
+            .sum()
+    }
+
+    // Copies the annotations of the previous version of this file onto the
+    // matching entries. An entry that moved to another defining pack (e.g.
+    // after `move` or `rename-pack`) keeps the annotations of its constant,
+    // as long as its old entry is gone and no other entry for the constant
+    // could have been the one that moved.
+    fn keep_annotations(&mut self, previous: &PackageTodo) {
+        let current_entries: HashSet<(String, String)> = self
+            .violations_by_defining_pack
+            .iter()
+            .flat_map(|(defining_pack_name, violation_groups)| {
+                violation_groups.keys().map(|constant_name| {
+                    (defining_pack_name.clone(), constant_name.clone())
+                })
+            })
+            .collect();
+        for (defining_pack_name, violation_groups) in
+            &mut self.violations_by_defining_pack
+        {
+            for (constant_name, violation_group) in violation_groups {
+                let previous_group = previous
+                    .violations_by_defining_pack
+                    .get(defining_pack_name)
+                    .and_then(|groups| groups.get(constant_name))
+                    .or_else(|| {
+                        let mut moved_groups = previous
+                            .violations_by_defining_pack
+                            .iter()
+                            .filter(|(previous_defining_pack_name, _)| {
+                                !current_entries.contains(&(
+                                    previous_defining_pack_name.to_string(),
+                                    constant_name.clone(),
+                                ))
+                            })
+                            .filter_map(|(_, groups)| {
+                                groups.get(constant_name)
+                            });
+                        match (moved_groups.next(), moved_groups.next()) {
+                            (Some(moved_group), None) => Some(moved_group),
+                            _ => None,
+                        }
+                    });
+                if let Some(previous_group) = previous_group {
+                    violation_group.keep_annotations(previous_group);
+                }
+            }
+        }
+    }
+}
+
 fn serialize_violations_by_defining_pack<S>(
     map: &BTreeMap<String, BTreeMap<String, ViolationGroup>>,
     serializer: S,
@@ -77,7 +163,10 @@ where
 }
 
 pub fn package_todos_for_pack_name(
-    violations_by_responsible_pack_name: HashMap<String, Vec<Violation>>,
+    violations_by_responsible_pack_name: HashMap<
+        String,
+        Vec<ViolationIdentifier>,
+    >,
 ) -> HashMap<String, PackageTodo> {
     let mut ret = HashMap::new();
 
@@ -92,33 +181,27 @@ pub fn package_todos_for_pack_name(
         // Sort violations by the defining pack name, then constant name, then file name
         // This ensures they show up deterministically in the package_todo.yml file.
         violations.sort_by(|a, b| {
-            a.identifier
-                .defining_pack_name
-                .cmp(&b.identifier.defining_pack_name)
-                .then_with(|| {
-                    a.identifier.constant_name.cmp(&b.identifier.constant_name)
-                })
-                .then_with(|| a.identifier.file.cmp(&b.identifier.file))
+            a.defining_pack_name
+                .cmp(&b.defining_pack_name)
+                .then_with(|| a.constant_name.cmp(&b.constant_name))
+                .then_with(|| a.file.cmp(&b.file))
         });
 
         for violation in violations {
-            let defining_pack_name =
-                violation.identifier.defining_pack_name.to_owned();
+            let defining_pack_name = violation.defining_pack_name.to_owned();
             let existing_violations_by_constant_group =
                 violations_by_defining_pack
                     .entry(defining_pack_name)
                     .or_default();
 
             let violation_group = existing_violations_by_constant_group
-                .entry(violation.identifier.constant_name.to_owned())
+                .entry(violation.constant_name.to_owned())
                 .or_default();
 
-            violation_group
-                .files
-                .insert(violation.identifier.file.to_owned());
+            violation_group.files.insert(violation.file.to_owned());
             violation_group
                 .violation_types
-                .insert(violation.identifier.violation_type.to_owned());
+                .insert(violation.violation_type.to_owned());
         }
 
         let package_todo = PackageTodo {
@@ -138,8 +221,10 @@ pub fn write_violations_to_disk(
     // First we need to group the violations by the repsonsible pack, which today is always the referencing pack
     // Later if we change where a violation shows up, we should delegate to the checker
     // to decide what pack it should be in.
-    let mut violations_by_responsible_pack: HashMap<String, Vec<Violation>> =
-        HashMap::new();
+    let mut violations_by_responsible_pack: HashMap<
+        String,
+        Vec<ViolationIdentifier>,
+    > = HashMap::new();
     for violation in violations {
         if violation.identifier.strict {
             continue;
@@ -149,7 +234,7 @@ pub fn write_violations_to_disk(
         violations_by_responsible_pack
             .entry(referencing_pack_name)
             .or_default()
-            .push(violation);
+            .push(violation.identifier);
     }
 
     let package_todos_by_pack_name =
@@ -171,6 +256,111 @@ pub fn write_violations_to_disk(
     debug!("Finished writing violations to disk");
 }
 
+// Rewrites the package_todo.yml files of the given packs from recorded
+// violation identifiers, leaving every other pack's file untouched
+pub(crate) fn write_violation_identifiers_to_disk(
+    configuration: &Configuration,
+    pack_names: &HashSet<String>,
+    identifiers: Vec<ViolationIdentifier>,
+) {
+    let mut violations_by_responsible_pack: HashMap<
+        String,
+        Vec<ViolationIdentifier>,
+    > = HashMap::new();
+    for identifier in identifiers {
+        violations_by_responsible_pack
+            .entry(identifier.referencing_pack_name.to_owned())
+            .or_default()
+            .push(identifier);
+    }
+
+    let package_todos_by_pack_name =
+        package_todos_for_pack_name(violations_by_responsible_pack);
+
+    for pack in &configuration.pack_set.packs {
+        if !pack_names.contains(&pack.name) {
+            continue;
+        }
+        match package_todos_by_pack_name.get(&pack.name) {
+            Some(package_todo) => write_package_todo_to_disk(
+                pack,
+                package_todo,
+                configuration.packs_first_mode,
+            ),
+            None => delete_package_todo_from_disk(pack),
+        }
+    }
+}
+
+pub(crate) fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+// Parses a `--within` duration such as `30d` or `2w` into days
+pub(crate) fn parse_days(value: &str) -> Result<i64, String> {
+    let (number, days_per_unit) = match value.strip_suffix('w') {
+        Some(weeks) => (weeks, 7),
+        None => (value.strip_suffix('d').unwrap_or(value), 1),
+    };
+    number
+        .parse::<i64>()
+        .ok()
+        .filter(|number| *number >= 0)
+        .map(|number| number * days_per_unit)
+        .ok_or_else(|| {
+            format!(
+                "`{}` is not a number of days (e.g. 30d) or weeks (e.g. 2w)",
+                value
+            )
+        })
+}
+
+// An annotated entry that expires on or before a date
+#[derive(Debug, PartialEq)]
+pub(crate) struct ExpiringEntry<'a> {
+    pub responsible_pack: &'a Pack,
+    pub defining_pack_name: &'a str,
+    pub constant_name: &'a str,
+    pub expires: NaiveDate,
+    pub violation_group: &'a ViolationGroup,
+}
+
+// Entries expiring on or before `until`, soonest first
+pub(crate) fn expiring_entries(
+    configuration: &Configuration,
+    until: NaiveDate,
+) -> Vec<ExpiringEntry> {
+    let mut entries = vec![];
+    for pack in &configuration.pack_set.packs {
+        for (defining_pack_name, violation_groups) in
+            &pack.package_todo.violations_by_defining_pack
+        {
+            for (constant_name, violation_group) in violation_groups {
+                match violation_group.expires {
+                    Some(expires) if expires <= until => {
+                        entries.push(ExpiringEntry {
+                            responsible_pack: pack,
+                            defining_pack_name,
+                            constant_name,
+                            expires,
+                            violation_group,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    entries.sort_by(|a, b| {
+        a.expires
+            .cmp(&b.expires)
+            .then_with(|| a.responsible_pack.name.cmp(&b.responsible_pack.name))
+            .then_with(|| a.defining_pack_name.cmp(b.defining_pack_name))
+            .then_with(|| a.constant_name.cmp(b.constant_name))
+    });
+    entries
+}
+
 fn serialize_package_todo(
     responsible_pack_name: &String,
     package_todo: &PackageTodo,
@@ -200,9 +390,11 @@ fn write_package_todo_to_disk(
         std::fs::File::create(&package_todo_yml_absolute_filepath).unwrap();
     }
 
+    let mut package_todo = package_todo.clone();
+    package_todo.keep_annotations(&responsible_pack.package_todo);
     let package_todo_yml = serialize_package_todo(
         &responsible_pack.name,
-        package_todo,
+        &package_todo,
         packs_first_mode,
     );
 
@@ -268,6 +460,7 @@ mod tests {
             ViolationGroup {
                 violation_types,
                 files,
+                ..ViolationGroup::default()
             },
         );
 
@@ -482,4 +675,104 @@ packs/bar:
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_deserialize_annotations() {
+        let contents = "
+packs/bar:
+  \"::Bar\":
+    violations:
+    - dependency
+    files:
+    - packs/foo/app/services/foo.rb
+    expires: 2027-01-31
+    owner: '@payments-team'
+    ticket: PAY-123
+";
+
+        let package_todo: PackageTodo = serde_yaml::from_str(contents).unwrap();
+        let violation_group =
+            &package_todo.violations_by_defining_pack["packs/bar"]["::Bar"];
+        assert_eq!(
+            violation_group.expires,
+            NaiveDate::from_ymd_opt(2027, 1, 31)
+        );
+        assert_eq!(violation_group.owner.as_deref(), Some("@payments-team"));
+        assert_eq!(violation_group.ticket.as_deref(), Some("PAY-123"));
+        assert!(!violation_group
+            .is_expired(NaiveDate::from_ymd_opt(2027, 1, 30).unwrap()));
+        assert!(violation_group
+            .is_expired(NaiveDate::from_ymd_opt(2027, 1, 31).unwrap()));
+    }
+
+    #[test]
+    fn test_keep_annotations() {
+        let mut previous = example_package_todo(String::from("packs/bar"));
+        for violation_group in previous
+            .violations_by_defining_pack
+            .get_mut("packs/bar")
+            .unwrap()
+            .values_mut()
+        {
+            violation_group.ticket = Some(String::from("PAY-123"));
+        }
+
+        // `::Baz` moved to another pack
+        let mut package_todo = PackageTodo::default();
+        package_todo
+            .violations_by_defining_pack
+            .insert(String::from("packs/bar"), bar_violations());
+        package_todo
+            .violations_by_defining_pack
+            .insert(String::from("packs/baz"), baz_violations());
+        package_todo.keep_annotations(&previous);
+
+        let tickets: Vec<Option<&str>> = package_todo
+            .violations_by_defining_pack
+            .values()
+            .flat_map(|groups| groups.values())
+            .map(|group| group.ticket.as_deref())
+            .collect();
+        assert_eq!(tickets, vec![Some("PAY-123"), Some("PAY-123")]);
+    }
+
+    #[test]
+    fn test_keep_annotations_of_coexisting_entries() {
+        let mut previous = PackageTodo::default();
+        previous
+            .violations_by_defining_pack
+            .insert(String::from("packs/bar"), bar_violations());
+        previous
+            .violations_by_defining_pack
+            .get_mut("packs/bar")
+            .unwrap()
+            .get_mut("::Bar")
+            .unwrap()
+            .expires = NaiveDate::from_ymd_opt(2020, 1, 1);
+
+        // A new violation on a constant with the same name in another pack,
+        // while the annotated entry stays
+        let mut package_todo = previous.clone();
+        package_todo
+            .violations_by_defining_pack
+            .insert(String::from("packs/baz"), bar_violations());
+        package_todo.keep_annotations(&previous);
+
+        let expires: Vec<Option<NaiveDate>> = package_todo
+            .violations_by_defining_pack
+            .values()
+            .flat_map(|groups| groups.values())
+            .map(|group| group.expires)
+            .collect();
+        assert_eq!(expires, vec![NaiveDate::from_ymd_opt(2020, 1, 1), None]);
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("30d"), Ok(30));
+        assert_eq!(parse_days("30"), Ok(30));
+        assert_eq!(parse_days("2w"), Ok(14));
+        assert!(parse_days("-1d").is_err());
+        assert!(parse_days("a month").is_err());
+    }
 }
@@ -0,0 +1,66 @@
+use regex::Regex;
+
+use crate::packs::parsing::source_map::{RubyBuilder, SourceMap};
+
+// Extracts the Ruby code of every `<% %>` tag, one tag after another
+pub(crate) fn convert_erb_to_ruby(contents: &str) -> (String, SourceMap) {
+    let regex = Regex::new(r"(?s)<%=?-?\s*(.*?)\s*-?%>").unwrap();
+
+    let mut ruby = RubyBuilder::default();
+    let mut erb_row = 1;
+    let mut erb_line_start = 0;
+    let mut scanned = 0;
+    for capture in regex.captures_iter(contents) {
+        let code = capture.get(1).unwrap();
+        for (index, _) in contents[scanned..code.start()].match_indices('\n') {
+            erb_row += 1;
+            erb_line_start = scanned + index + 1;
+        }
+        scanned = code.start();
+
+        ruby.push(code.as_str(), erb_row, code.start() - erb_line_start);
+    }
+
+    ruby.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::parsing::Range;
+
+    #[test]
+    fn test_convert_erb_to_ruby() {
+        let (ruby, source_map) =
+            convert_erb_to_ruby("<h1><%= Foo %></h1>\n  <%- if x\n  Bar %>");
+        assert_eq!(ruby, "Foo\nif x\n  Bar");
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 1,
+                start_col: 0,
+                end_row: 1,
+                end_col: 4,
+            }),
+            Range {
+                start_row: 1,
+                start_col: 8,
+                end_row: 1,
+                end_col: 12,
+            }
+        );
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 3,
+                start_col: 2,
+                end_row: 3,
+                end_col: 6,
+            }),
+            Range {
+                start_row: 3,
+                start_col: 2,
+                end_row: 3,
+                end_col: 6,
+            }
+        );
+    }
+}
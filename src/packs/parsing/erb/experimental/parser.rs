@@ -1,7 +1,8 @@
 use crate::packs::file_utils::file_read_contents;
 use crate::packs::{
-    file_utils::convert_erb_to_ruby_without_sourcemaps, parsing::Range,
-    Configuration, ProcessedFile, UnresolvedReference,
+    parsing::erb::converter::convert_erb_to_ruby,
+    parsing::source_map::process_template_contents, Configuration,
+    ProcessedFile,
 };
 use std::path::Path;
 
@@ -20,25 +21,11 @@ pub(crate) fn process_from_contents(
     path: &Path,
     configuration: &Configuration,
 ) -> ProcessedFile {
-    let ruby_contents = convert_erb_to_ruby_without_sourcemaps(contents);
-    let processed_file =
-        process_from_ruby_contents(ruby_contents, path, configuration);
-    let references = processed_file.unresolved_references;
-    // let references_without_range = references
-    let references_without_range = references
-        .iter()
-        .map(|r| UnresolvedReference {
-            // Source maps are not yet supported for ERB, since we just turn it into Ruby code
-            // that doesn't necessarily map up to the original.
-            // We need to add extra logic to support source maps (or use a proper parsing library).
-            location: Range::default(),
-            ..r.clone()
-        })
-        .collect();
-
-    ProcessedFile {
-        absolute_path: path.to_path_buf(),
-        unresolved_references: references_without_range,
-        definitions: vec![],
-    }
+    process_template_contents(
+        contents,
+        path,
+        configuration,
+        convert_erb_to_ruby,
+        process_from_ruby_contents,
+    )
 }
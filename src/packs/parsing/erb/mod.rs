@@ -1,2 +1,3 @@
+pub(crate) mod converter;
 pub(crate) mod experimental;
 pub(crate) mod packwerk;
@@ -18,7 +18,13 @@ mod tests {
             vec![UnresolvedReference {
                 name: String::from("Foo"),
                 namespace_path: vec![],
-                location: Range::default()
+                location: Range {
+                    start_row: 1,
+                    start_col: 4,
+                    end_row: 1,
+                    end_col: 8,
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -38,12 +44,24 @@ mod tests {
                 UnresolvedReference {
                     name: String::from("Foo"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 1,
+                        start_col: 4,
+                        end_row: 1,
+                        end_col: 8,
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("Bar"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 1,
+                        start_col: 14,
+                        end_row: 1,
+                        end_col: 18,
+                    },
+                    suppressions: vec![],
                 }
             ],
             process_from_contents(
@@ -69,7 +87,13 @@ mod tests {
             vec![UnresolvedReference {
                 name: String::from("Foo"),
                 namespace_path: vec![],
-                location: Range::default()
+                location: Range {
+                    start_row: 3,
+                    start_col: 4,
+                    end_row: 3,
+                    end_col: 8,
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -94,7 +118,13 @@ mod tests {
             vec![UnresolvedReference {
                 name: String::from("Foo"),
                 namespace_path: vec![],
-                location: Range::default()
+                location: Range {
+                    start_row: 2,
+                    start_col: 6,
+                    end_row: 2,
+                    end_col: 10,
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -126,7 +156,13 @@ mod tests {
             vec![UnresolvedReference {
                 name: String::from("Foo"),
                 namespace_path: vec![],
-                location: Range::default()
+                location: Range {
+                    start_row: 2,
+                    start_col: 3,
+                    end_row: 2,
+                    end_col: 7,
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -155,7 +191,13 @@ mod tests {
             vec![UnresolvedReference {
                 name: String::from("Foo"),
                 namespace_path: vec![],
-                location: Range::default()
+                location: Range {
+                    start_row: 5,
+                    start_col: 4,
+                    end_row: 5,
+                    end_col: 8,
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -203,27 +245,57 @@ mod tests {
                 UnresolvedReference {
                     name: String::from("Foo"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 8,
+                        start_col: 8,
+                        end_row: 8,
+                        end_col: 12,
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("Bar"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 14,
+                        start_col: 12,
+                        end_row: 14,
+                        end_col: 16,
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("Baz"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 16,
+                        start_col: 9,
+                        end_row: 16,
+                        end_col: 13,
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("Boo"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 22,
+                        start_col: 14,
+                        end_row: 22,
+                        end_col: 18,
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("Bee"),
                     namespace_path: vec![],
-                    location: Range::default()
+                    location: Range {
+                        start_row: 23,
+                        start_col: 21,
+                        end_row: 23,
+                        end_col: 25,
+                    },
+                    suppressions: vec![],
                 }
             ],
             process_from_contents(
@@ -0,0 +1,205 @@
+use super::source_map::{RubyBuilder, SourceMap};
+use super::template::{
+    code_with_continuations, end_of_group, end_of_nested_lines,
+    push_interpolations, template_lines, Blocks,
+};
+
+// Extracts the Ruby code of `- code` and `= code` lines (also `!=`, `&=` and
+// `~`, on their own or after a tag), `#{}` interpolations and `:ruby`
+// filters. `-#` comments and the lines nested under them are skipped.
+pub(crate) fn convert_haml_to_ruby(contents: &str) -> (String, SourceMap) {
+    let lines = template_lines(contents);
+    let mut ruby = RubyBuilder::default();
+    let mut blocks = Blocks::default();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        if line.text.is_empty() {
+            index += 1;
+            continue;
+        }
+
+        if line.text.starts_with("-#") {
+            blocks.close(&mut ruby, line.indent, None);
+            index = end_of_nested_lines(&lines, index + 1, line.indent);
+            continue;
+        }
+
+        if line.text.starts_with(':') {
+            blocks.close(&mut ruby, line.indent, None);
+            let end = end_of_nested_lines(&lines, index + 1, line.indent);
+            for nested in lines[index + 1..end].iter() {
+                if nested.text.is_empty() {
+                    continue;
+                }
+                if line.text == ":ruby" {
+                    ruby.push(nested.text, nested.row, nested.indent);
+                } else {
+                    push_interpolations(
+                        &mut ruby,
+                        nested.text,
+                        nested.row,
+                        nested.indent,
+                    );
+                }
+            }
+            index = end;
+            continue;
+        }
+
+        match code_offset(line.text) {
+            Some(offset) => {
+                let (code, next_index) = code_with_continuations(
+                    contents,
+                    &lines,
+                    index,
+                    line.start + offset,
+                    &[','],
+                );
+                blocks.close(&mut ruby, line.indent, Some(code));
+                ruby.push(code, line.row, line.indent + offset);
+                blocks.open(line.indent, code);
+                index = next_index;
+            }
+            None => {
+                blocks.close(&mut ruby, line.indent, None);
+                push_interpolations(
+                    &mut ruby,
+                    line.text,
+                    line.row,
+                    line.indent,
+                );
+                index += 1;
+            }
+        }
+    }
+    blocks.close_all(&mut ruby);
+
+    ruby.build()
+}
+
+// Where the Ruby code of a line starts, if the line has any
+fn code_offset(text: &str) -> Option<usize> {
+    let marker_end = if text.starts_with('-') {
+        1
+    } else if text.starts_with(['%', '.'])
+        || (text.starts_with('#') && !text.starts_with("#{"))
+    {
+        let tag_end = end_of_tag(text);
+        tag_end + output_marker_len(&text[tag_end..])?
+    } else {
+        output_marker_len(text)?
+    };
+    let code = &text[marker_end..];
+    Some(marker_end + code.len() - code.trim_start().len())
+}
+
+// `==` (and `!==`, `&==`) output interpolated text rather than Ruby code
+fn output_marker_len(text: &str) -> Option<usize> {
+    ["!=", "&=", "=", "~"]
+        .into_iter()
+        .find(|marker| text.starts_with(marker))
+        .filter(|marker| !text[marker.len()..].starts_with('='))
+        .map(|marker| marker.len())
+}
+
+// The end of `%tag.class#id{attributes}(attributes)[object]<>`
+fn end_of_tag(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() && matches!(bytes[index], b'%' | b'.' | b'#') {
+        index += 1;
+        while index < bytes.len()
+            && (bytes[index].is_ascii_alphanumeric()
+                || matches!(bytes[index], b'_' | b'-' | b':'))
+        {
+            index += 1;
+        }
+    }
+    while index < bytes.len() && matches!(bytes[index], b'{' | b'(' | b'[') {
+        index = end_of_group(text, index);
+    }
+    while index < bytes.len() && matches!(bytes[index], b'<' | b'>' | b'/') {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::parsing::Range;
+
+    #[test]
+    fn test_convert_haml_to_ruby() {
+        let contents = "\
+%div.widgets{ class: 'x' }
+  - if Foo.enabled?
+    %p= Bar.title
+  - else
+    -# = Hidden.call
+      Still hidden
+    %p Hello #{User.name}
+  - Widget.all.each do |widget|
+    = render widget,
+      locals: { a: 1 }
+:ruby
+  Baz.call
+:javascript
+  var id = #{Qux.id};
+%p== Not #{Code.here}
+";
+        let (ruby, _) = convert_haml_to_ruby(contents);
+        assert_eq!(
+            ruby,
+            "\
+if Foo.enabled?
+Bar.title
+else
+User.name
+end
+Widget.all.each do |widget|
+render widget,
+      locals: { a: 1 }
+end
+Baz.call
+Qux.id
+Code.here"
+        );
+    }
+
+    #[test]
+    fn test_source_map() {
+        let contents = "%h1 Title\n  %p= Bar.title\n  %p Hi #{User.name}\n";
+        let (ruby, source_map) = convert_haml_to_ruby(contents);
+        assert_eq!(ruby, "Bar.title\nUser.name");
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 1,
+                start_col: 0,
+                end_row: 1,
+                end_col: 4,
+            }),
+            Range {
+                start_row: 2,
+                start_col: 6,
+                end_row: 2,
+                end_col: 10,
+            }
+        );
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 2,
+                start_col: 0,
+                end_row: 2,
+                end_col: 5,
+            }),
+            Range {
+                start_row: 3,
+                start_col: 10,
+                end_row: 3,
+                end_col: 15,
+            }
+        );
+    }
+}
@@ -4,15 +4,26 @@ use std::{
 };
 
 pub(crate) mod ruby;
+pub(crate) use ruby::experimental::parser::process_from_contents as process_from_ruby_contents_experimental;
 pub(crate) use ruby::experimental::parser::process_from_path as process_from_ruby_path_experimental;
+pub(crate) use ruby::packwerk::parser::process_from_contents as process_from_ruby_contents;
 pub(crate) use ruby::packwerk::parser::process_from_path as process_from_ruby_path;
 mod erb;
+mod haml;
+mod slim;
+pub(crate) mod source_map;
+pub(crate) mod suppression;
+mod template;
+pub(crate) use erb::experimental::parser::process_from_contents as process_from_erb_contents_experimental;
 pub(crate) use erb::experimental::parser::process_from_path as process_from_erb_path_experimental;
+pub(crate) use erb::packwerk::parser::process_from_contents as process_from_erb_contents;
 pub(crate) use erb::packwerk::parser::process_from_path as process_from_erb_path;
 
 use crate::packs::file_utils::is_stdin_file;
+use haml::convert_haml_to_ruby;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
+use slim::convert_slim_to_ruby;
 
 use super::{
     caching::{cache::Cache, CacheResult},
@@ -45,6 +56,16 @@ pub fn process_file(
                     process_from_erb_path(path, configuration)
                 }
             }
+            SupportedFileType::Haml => template::process_from_path(
+                path,
+                configuration,
+                convert_haml_to_ruby,
+            ),
+            SupportedFileType::Slim => template::process_from_path(
+                path,
+                configuration,
+                convert_slim_to_ruby,
+            ),
         }
     } else {
         // Later, we can perhaps have this error, since in theory the Configuration.intersect
@@ -53,6 +74,7 @@ pub fn process_file(
             absolute_path: path.to_path_buf(),
             unresolved_references: vec![],
             definitions: vec![], // TODO
+            suppressions: vec![],
         })
     };
 
@@ -63,11 +85,74 @@ pub fn process_file(
     result
 }
 
+// Like `process_file`, but for contents that may not have been saved to
+// disk yet (e.g. an editor buffer)
+pub(crate) fn process_file_contents(
+    path: &Path,
+    contents: String,
+    configuration: &Configuration,
+) -> ProcessedFile {
+    match get_file_type(path) {
+        Some(SupportedFileType::Ruby) => {
+            if configuration.experimental_parser {
+                process_from_ruby_contents_experimental(
+                    contents,
+                    path,
+                    configuration,
+                )
+            } else {
+                process_from_ruby_contents(contents, path, configuration)
+            }
+        }
+        Some(SupportedFileType::Erb) => {
+            if configuration.experimental_parser {
+                process_from_erb_contents_experimental(
+                    contents,
+                    path,
+                    configuration,
+                )
+            } else {
+                process_from_erb_contents(contents, path, configuration)
+            }
+        }
+        Some(SupportedFileType::Haml) => template::process_from_contents(
+            contents,
+            path,
+            configuration,
+            convert_haml_to_ruby,
+        ),
+        Some(SupportedFileType::Slim) => template::process_from_contents(
+            contents,
+            path,
+            configuration,
+            convert_slim_to_ruby,
+        ),
+        None => ProcessedFile {
+            absolute_path: path.to_path_buf(),
+            unresolved_references: vec![],
+            definitions: vec![],
+            suppressions: vec![],
+        },
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct UnresolvedReference {
     pub name: String,
     pub namespace_path: Vec<String>,
     pub location: Range,
+    // `pks:disable-next-line` comments on the line above the reference
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<Suppression>,
+}
+
+// A `# pks:disable-next-line <violation types> -- <reason>` comment
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Hash)]
+pub struct Suppression {
+    // The 1-based line of the comment, so it disables `line + 1`
+    pub line: usize,
+    pub violation_types: Vec<String>,
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Default)]
@@ -143,4 +228,16 @@ mod tests {
     fn identifies_erb_files() {
         assert_is_erb("foo.erb");
     }
+
+    #[test]
+    fn identifies_haml_and_slim_files() {
+        assert_eq!(
+            Some(SupportedFileType::Haml),
+            get_file_type(Path::new("show.html.haml"))
+        );
+        assert_eq!(
+            Some(SupportedFileType::Slim),
+            get_file_type(Path::new("show.html.slim"))
+        );
+    }
 }
@@ -69,6 +69,7 @@ mod tests {
                 end_row: 1,
                 end_col: 4,
             },
+            suppressions: vec![],
         }];
 
         let definitions = vec![];
@@ -79,6 +80,7 @@ mod tests {
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -98,6 +100,7 @@ mod tests {
                 end_row: 1,
                 end_col: 9,
             },
+            suppressions: vec![],
         }];
 
         let definitions = vec![];
@@ -108,6 +111,7 @@ mod tests {
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -128,6 +132,7 @@ mod tests {
                 end_row: 1,
                 end_col: 14,
             },
+            suppressions: vec![],
         }];
 
         let definitions = vec![];
@@ -138,6 +143,7 @@ mod tests {
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -158,6 +164,7 @@ mod tests {
                 end_row: 1,
                 end_col: 19,
             },
+            suppressions: vec![],
         }];
 
         let definitions = vec![];
@@ -168,6 +175,7 @@ mod tests {
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -194,6 +202,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -230,6 +239,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -266,6 +276,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -301,6 +312,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -340,6 +352,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -380,6 +393,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
 
         assert_eq!(expected, actual);
@@ -421,6 +435,7 @@ end
             absolute_path,
             unresolved_references,
             definitions,
+            suppressions: vec![],
         };
 
         assert_eq!(expected, actual);
@@ -6,6 +6,7 @@ use crate::packs::{
             get_constant_assignment_definition, get_definition_from,
             get_reference_from_active_record_association, loc_to_range,
         },
+        suppression::{attach_suppressions, find_suppressions},
         ParsedDefinition, UnresolvedReference,
     },
     Configuration, ProcessedFile,
@@ -165,6 +166,7 @@ impl<'a> Visitor for ReferenceCollector<'a> {
             name,
             namespace_path,
             location: loc_to_range(&node.expression_l, &self.line_col_lookup),
+            suppressions: vec![],
         })
     }
 
@@ -210,6 +212,7 @@ pub(crate) fn process_from_contents(
                 absolute_path: path.to_owned(),
                 unresolved_references: vec![],
                 definitions: vec![],
+                suppressions: vec![],
             }
         }
     };
@@ -225,7 +228,9 @@ pub(crate) fn process_from_contents(
 
     collector.visit(&ast);
 
-    let unresolved_references = collector.references;
+    let mut unresolved_references = collector.references;
+    let suppressions = find_suppressions(&contents);
+    attach_suppressions(&mut unresolved_references, &suppressions);
 
     let absolute_path = path.to_owned();
 
@@ -238,5 +243,6 @@ pub(crate) fn process_from_contents(
         absolute_path,
         unresolved_references,
         definitions,
+        suppressions,
     }
 }
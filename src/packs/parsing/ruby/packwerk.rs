@@ -21,7 +21,8 @@ mod tests {
                     start_col: 0,
                     end_row: 1,
                     end_col: 4
-                }
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -45,7 +46,8 @@ mod tests {
                     start_col: 0,
                     end_row: 1,
                     end_col: 9
-                }
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -69,7 +71,8 @@ mod tests {
                     start_col: 0,
                     end_row: 1,
                     end_col: 14
-                }
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -93,7 +96,8 @@ mod tests {
                     start_col: 0,
                     end_row: 1,
                     end_col: 19
-                }
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -123,7 +127,8 @@ end
                     start_col: 6,
                     end_row: 1,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             }],
             process_from_contents(
                 contents,
@@ -154,7 +159,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 6
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -189,7 +195,8 @@ end
                     start_col: 4,
                     end_row: 3,
                     end_col: 8
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -230,7 +237,8 @@ end
                     start_col: 6,
                     end_row: 4,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -264,7 +272,8 @@ end
                         start_col: 7,
                         end_row: 1,
                         end_col: 11
-                    }
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("Bar"),
@@ -274,7 +283,8 @@ end
                         start_col: 2,
                         end_row: 2,
                         end_col: 6
-                    }
+                    },
+                    suppressions: vec![],
                 }
             ],
             process_from_contents(
@@ -308,7 +318,8 @@ end
                     start_col: 4,
                     end_row: 3,
                     end_col: 8
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -349,7 +360,8 @@ end
                     start_col: 6,
                     end_row: 4,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -391,6 +403,7 @@ end
                     end_row: 4,
                     end_col: 10
                 },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -424,7 +437,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 6
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -459,7 +473,8 @@ end
                     start_col: 9,
                     end_row: 2,
                     end_col: 13
-                }
+                },
+                suppressions: vec![],
             },
             *process_from_contents(
                 contents,
@@ -499,7 +514,8 @@ end
                     start_col: 1,
                     end_row: 1,
                     end_col: 5
-                }
+                },
+                suppressions: vec![],
             },
             *reference
         );
@@ -530,7 +546,8 @@ end
                     start_col: 1,
                     end_row: 1,
                     end_col: 5
-                }
+                },
+                suppressions: vec![],
             },
             *reference1
         );
@@ -547,7 +564,8 @@ end
                     start_col: 6,
                     end_row: 1,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             },
             *reference2,
         );
@@ -579,7 +597,8 @@ end
                     start_col: 1,
                     end_row: 1,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             },
             *reference,
         );
@@ -611,7 +630,8 @@ end
                     start_col: 0,
                     end_row: 1,
                     end_col: 6
-                }
+                },
+                suppressions: vec![],
             },
             *reference,
         );
@@ -662,7 +682,8 @@ end
                     start_col: 6,
                     end_row: 1,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             }]
         )
     }
@@ -699,7 +720,8 @@ end
                         start_col: 6,
                         end_row: 1,
                         end_col: 10
-                    }
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("::Foo::Baz"),
@@ -709,7 +731,8 @@ end
                         start_col: 8,
                         end_row: 2,
                         end_col: 12
-                    }
+                    },
+                    suppressions: vec![],
                 }
             ]
         );
@@ -745,7 +768,8 @@ end
                     start_col: 12,
                     end_row: 1,
                     end_col: 16
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -780,7 +804,8 @@ end
                     start_col: 6,
                     end_row: 1,
                     end_col: 15
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -816,7 +841,8 @@ end
                         start_col: 6,
                         end_row: 1,
                         end_col: 10
-                    }
+                    },
+                    suppressions: vec![],
                 },
                 UnresolvedReference {
                     name: String::from("::Foo::Bar"),
@@ -826,7 +852,8 @@ end
                         start_col: 8,
                         end_row: 2,
                         end_col: 12
-                    }
+                    },
+                    suppressions: vec![],
                 }
             ]
         );
@@ -861,7 +888,8 @@ FOO = BAR
                     start_col: 6,
                     end_row: 1,
                     end_col: 10
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference
         )
@@ -900,7 +928,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 27
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -939,7 +968,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 47
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -977,7 +1007,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 29
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1016,7 +1047,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 24
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1052,7 +1084,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 21
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1089,7 +1122,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 22
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1125,7 +1159,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 17
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1164,7 +1199,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 25
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1200,7 +1236,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 72
-                }
+                },
+                suppressions: vec![],
             },
             *first_reference,
         );
@@ -1238,7 +1275,8 @@ end
                     start_col: 2,
                     end_row: 2,
                     end_col: 6
-                }
+                },
+                suppressions: vec![],
             },
             *reference,
         );
@@ -1305,7 +1343,8 @@ end
                     start_col: 6,
                     end_row: 1,
                     end_col: 15
-                }
+                },
+                suppressions: vec![],
             },
             *reference,
         );
@@ -9,6 +9,7 @@ use crate::packs::{
                 get_reference_from_active_record_association, loc_to_range,
             },
         },
+        suppression::{attach_suppressions, find_suppressions},
         ParsedDefinition, Range, UnresolvedReference,
     },
     Configuration, ProcessedFile,
@@ -71,6 +72,7 @@ impl<'a> Visitor for ReferenceCollector<'a> {
             name,
             namespace_path,
             location,
+            suppressions: vec![],
         });
 
         // Note – is there a way to use lifetime specifiers to get rid of this and
@@ -143,6 +145,7 @@ impl<'a> Visitor for ReferenceCollector<'a> {
             name,
             namespace_path,
             location,
+            suppressions: vec![],
         });
 
         // Note – is there a way to use lifetime specifiers to get rid of this and
@@ -196,6 +199,7 @@ impl<'a> Visitor for ReferenceCollector<'a> {
             name,
             namespace_path,
             location: loc_to_range(&node.expression_l, &self.line_col_lookup),
+            suppressions: vec![],
         })
     }
 }
@@ -231,6 +235,7 @@ pub(crate) fn process_from_contents(
                 absolute_path: path.to_owned(),
                 unresolved_references: vec![],
                 definitions: vec![],
+                suppressions: vec![],
             }
         }
     };
@@ -270,7 +275,7 @@ pub(crate) fn process_from_contents(
         }
     }
 
-    let unresolved_references = collector
+    let mut unresolved_references: Vec<UnresolvedReference> = collector
         .references
         .into_iter()
         .filter(|r| {
@@ -300,6 +305,9 @@ pub(crate) fn process_from_contents(
         })
         .collect();
 
+    let suppressions = find_suppressions(&contents);
+    attach_suppressions(&mut unresolved_references, &suppressions);
+
     let absolute_path = path.to_owned();
 
     // The packwerk parser uses a ConstantResolver constructed by constants inferred from the file system
@@ -311,5 +319,6 @@ pub(crate) fn process_from_contents(
         absolute_path,
         unresolved_references,
         definitions,
+        suppressions,
     }
 }
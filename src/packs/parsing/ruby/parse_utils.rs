@@ -157,6 +157,7 @@ pub fn get_reference_from_active_record_association(
                 name: unwrapped_name,
                 namespace_path: current_namespaces.to_owned(),
                 location: loc_to_range(&node.expression_l, line_col_lookup),
+                suppressions: vec![],
             })
         } else {
             None
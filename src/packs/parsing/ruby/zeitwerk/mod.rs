@@ -92,6 +92,26 @@ fn get_pack_namespace_settings(pack: &Pack) -> PackNamespaceSettings {
         }) // Default to false and empty set if metadata doesn't exist
 }
 
+// Whether constants in the pack's autoload roots are namespaced by the pack
+// name, i.e. `metadata.automatic_pack_namespace: true`
+pub(crate) fn has_automatic_pack_namespace(pack: &Pack) -> bool {
+    get_pack_namespace_settings(pack).automatic_pack_namespace
+}
+
+pub(crate) fn automatic_namespace_for_pack(pack: &Pack) -> String {
+    // Pass an empty set of acronyms as the second argument
+    // NOTE: This is not the correct implementation – if we want automatic namespacing to work with
+    // acronym-based pack names, we need to pull from the file, preferably from the cache.
+    let empty_acronyms = HashSet::new();
+
+    // Camelized pack namespace based on pack name with leading double colon:
+    // e.g. pack name "packs/my_pack" -> "::MyPack"
+    format!(
+        "::{}",
+        inflector_shim::camelize(pack.last_name(), &empty_acronyms)
+    )
+}
+
 fn inferred_constants_from_pack_set(
     pack_set: &PackSet,
     configuration: &ConstantResolverConfiguration,
@@ -123,20 +143,7 @@ fn inferred_constants_from_pack_set(
                 if automatic_pack_namespace
                     && !automatic_pack_namespace_exclusions.contains(&path)
                 {
-                    // Pass an empty set of acronyms as the second argument
-                    // NOTE: This is not the correct implementation – if we want automatic namespacing to work with
-                    // acronym-based pack names, we need to pull from the file, preferably from the cache.
-                    let empty_acronyms = HashSet::new();
-
-                    // Camelized pack namespace based on pack name with leading double colon:
-                    // e.g. pack name "packs/my_pack" -> "::MyPack"
-                    let namespace = format!(
-                        "::{}",
-                        inflector_shim::camelize(
-                            pack.last_name(),
-                            &empty_acronyms,
-                        )
-                    );
+                    let namespace = automatic_namespace_for_pack(pack);
 
                     (path, namespace)
                 } else {
@@ -0,0 +1,245 @@
+use super::source_map::{RubyBuilder, SourceMap};
+use super::template::{
+    code_with_continuations, end_of_group, end_of_nested_lines,
+    push_interpolations, template_lines, Blocks,
+};
+
+// Extracts the Ruby code of `- code` and `= code` lines (also `==` and the
+// whitespace variants `=>`, `=<` and `='`, on their own or after a tag),
+// `#{}` interpolations and `ruby:` blocks. `/` comments and the lines nested
+// under them are skipped.
+pub(crate) fn convert_slim_to_ruby(contents: &str) -> (String, SourceMap) {
+    let lines = template_lines(contents);
+    let mut ruby = RubyBuilder::default();
+    let mut blocks = Blocks::default();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        if line.text.is_empty() {
+            index += 1;
+            continue;
+        }
+
+        // Code comments, but not HTML comments (`/!`) or conditional comments
+        // (`/[if IE]`)
+        if line.text.starts_with('/')
+            && !line.text.starts_with("/!")
+            && !line.text.starts_with("/[")
+        {
+            blocks.close(&mut ruby, line.indent, None);
+            index = end_of_nested_lines(&lines, index + 1, line.indent);
+            continue;
+        }
+
+        // Text blocks and embedded engines (`javascript:`, `ruby:`) continue
+        // on the nested lines
+        let is_text_block = line.text.starts_with(['|', '\'']);
+        if is_text_block || is_embedded_engine(line.text) {
+            blocks.close(&mut ruby, line.indent, None);
+            let end = end_of_nested_lines(&lines, index + 1, line.indent);
+            if is_text_block {
+                push_interpolations(
+                    &mut ruby,
+                    line.text,
+                    line.row,
+                    line.indent,
+                );
+            }
+            for nested in lines[index + 1..end].iter() {
+                if nested.text.is_empty() {
+                    continue;
+                }
+                if line.text == "ruby:" {
+                    ruby.push(nested.text, nested.row, nested.indent);
+                } else {
+                    push_interpolations(
+                        &mut ruby,
+                        nested.text,
+                        nested.row,
+                        nested.indent,
+                    );
+                }
+            }
+            index = end;
+            continue;
+        }
+
+        match code_offset(line.text) {
+            Some(offset) => {
+                let (code, next_index) = code_with_continuations(
+                    contents,
+                    &lines,
+                    index,
+                    line.start + offset,
+                    &[',', '\\'],
+                );
+                blocks.close(&mut ruby, line.indent, Some(code));
+                ruby.push(code, line.row, line.indent + offset);
+                blocks.open(line.indent, code);
+                index = next_index;
+            }
+            None => {
+                blocks.close(&mut ruby, line.indent, None);
+                push_interpolations(
+                    &mut ruby,
+                    line.text,
+                    line.row,
+                    line.indent,
+                );
+                index += 1;
+            }
+        }
+    }
+    blocks.close_all(&mut ruby);
+
+    ruby.build()
+}
+
+fn is_embedded_engine(text: &str) -> bool {
+    text.strip_suffix(':').map_or(false, |name| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+// Where the Ruby code of a line starts, if the line has any
+fn code_offset(text: &str) -> Option<usize> {
+    let marker_end = if text.starts_with('-') {
+        1
+    } else if text.starts_with('=') {
+        output_marker_len(text)
+    } else if text.starts_with(|c: char| c.is_ascii_alphabetic())
+        || text.starts_with(['.', '#', '*'])
+    {
+        let tag_end = end_of_tag(text);
+        let rest = &text[tag_end..];
+        let output = rest.trim_start();
+        if !output.starts_with('=') {
+            return None;
+        }
+        tag_end + rest.len() - output.len() + output_marker_len(output)
+    } else {
+        return None;
+    };
+    let code = &text[marker_end..];
+    Some(marker_end + code.len() - code.trim_start().len())
+}
+
+// `=`, `==`, followed by any of the whitespace modifiers `<`, `>` and `'`
+fn output_marker_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut index = 1;
+    if bytes.get(index) == Some(&b'=') {
+        index += 1;
+    }
+    while matches!(bytes.get(index), Some(b'<' | b'>' | b'\'')) {
+        index += 1;
+    }
+    index
+}
+
+// The end of `tag.class#id(attributes)<>`
+fn end_of_tag(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let is_name_byte = |byte: u8| {
+        byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-' | b':')
+    };
+    let mut index = 0;
+    while index < bytes.len() && is_name_byte(bytes[index]) {
+        index += 1;
+    }
+    while index < bytes.len() && matches!(bytes[index], b'.' | b'#') {
+        index += 1;
+        while index < bytes.len() && is_name_byte(bytes[index]) {
+            index += 1;
+        }
+    }
+    while index < bytes.len() && matches!(bytes[index], b'{' | b'(' | b'[') {
+        index = end_of_group(text, index);
+    }
+    while index < bytes.len() && matches!(bytes[index], b'<' | b'>') {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::parsing::Range;
+
+    #[test]
+    fn test_convert_slim_to_ruby() {
+        let contents = "\
+doctype html
+div.widgets
+  - if Foo.enabled?
+    p = Bar.title
+  - else
+    / = Hidden.call
+      Still hidden
+    p Hello #{User.name}
+  - Widget.all.each do |widget|
+    == render widget,
+      locals: { a: 1 }
+  | Some text
+    spanning #{Text.lines}
+ruby:
+  Baz.call
+javascript:
+  var id = #{Qux.id};
+a href=\"/\" = Link.text
+";
+        let (ruby, _) = convert_slim_to_ruby(contents);
+        assert_eq!(
+            ruby,
+            "\
+if Foo.enabled?
+Bar.title
+else
+User.name
+end
+Widget.all.each do |widget|
+render widget,
+      locals: { a: 1 }
+end
+Text.lines
+Baz.call
+Qux.id"
+        );
+    }
+
+    #[test]
+    fn test_source_map() {
+        let contents = "h1 Title\n  p= Bar.title\n  p Hi #{User.name}\n";
+        let (ruby, source_map) = convert_slim_to_ruby(contents);
+        assert_eq!(ruby, "Bar.title\nUser.name");
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 1,
+                start_col: 0,
+                end_row: 1,
+                end_col: 4,
+            }),
+            Range {
+                start_row: 2,
+                start_col: 5,
+                end_row: 2,
+                end_col: 9,
+            }
+        );
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 2,
+                start_col: 0,
+                end_row: 2,
+                end_col: 5,
+            }),
+            Range {
+                start_row: 3,
+                start_col: 9,
+                end_row: 3,
+                end_col: 14,
+            }
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::path::Path;
+
+use super::suppression::{attach_suppressions, find_suppressions};
+use super::Range;
+use crate::packs::{Configuration, ProcessedFile, UnresolvedReference};
+
+// Extracts the Ruby code of a template (ERB, HAML, Slim)
+pub(crate) type ConvertToRuby = fn(&str) -> (String, SourceMap);
+
+// Parses Ruby code, with the packwerk or the experimental parser
+pub(crate) type ProcessRuby =
+    fn(String, &Path, &Configuration) -> ProcessedFile;
+
+// Parses the Ruby code of a template, with references located in the
+// template rather than in the extracted Ruby code
+pub(crate) fn process_template_contents(
+    contents: String,
+    path: &Path,
+    configuration: &Configuration,
+    convert_to_ruby: ConvertToRuby,
+    process_ruby: ProcessRuby,
+) -> ProcessedFile {
+    let suppressions = find_suppressions(&contents);
+    let (ruby_contents, source_map) = convert_to_ruby(&contents);
+    let processed_file = process_ruby(ruby_contents, path, configuration);
+    let mut references: Vec<UnresolvedReference> = processed_file
+        .unresolved_references
+        .into_iter()
+        .map(|reference| UnresolvedReference {
+            location: source_map.map_range(&reference.location),
+            ..reference
+        })
+        .collect();
+
+    attach_suppressions(&mut references, &suppressions);
+
+    ProcessedFile {
+        absolute_path: path.to_path_buf(),
+        unresolved_references: references,
+        definitions: vec![],
+        suppressions,
+    }
+}
+
+// Where one snippet of Ruby code starts, both in the Ruby code extracted from
+// a template and in the template itself
+#[derive(Debug, PartialEq, Eq)]
+struct Snippet {
+    ruby_row: usize,
+    template_row: usize,
+    template_col: usize,
+}
+
+// Maps locations in the Ruby code extracted from a template (ERB, HAML,
+// Slim) back to the template
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SourceMap {
+    // Sorted by `ruby_row`
+    snippets: Vec<Snippet>,
+}
+
+// Builds the Ruby code of a template one snippet per line. Snippets are
+// copied verbatim, so only the first line of a snippet is shifted in the
+// template; the rest of its lines keep their columns.
+#[derive(Debug, Default)]
+pub(crate) struct RubyBuilder<'a> {
+    code: Vec<&'a str>,
+    rows: usize,
+    source_map: SourceMap,
+}
+
+impl<'a> RubyBuilder<'a> {
+    pub(crate) fn push(
+        &mut self,
+        code: &'a str,
+        template_row: usize,
+        template_col: usize,
+    ) {
+        self.source_map.snippets.push(Snippet {
+            ruby_row: self.rows + 1,
+            template_row,
+            template_col,
+        });
+        self.push_generated(code);
+    }
+
+    // Code that isn't in the template, such as the `end` of a HAML block
+    pub(crate) fn push_generated(&mut self, code: &'a str) {
+        self.rows += code.matches('\n').count() + 1;
+        self.code.push(code);
+    }
+
+    pub(crate) fn build(self) -> (String, SourceMap) {
+        (self.code.join("\n"), self.source_map)
+    }
+}
+
+impl SourceMap {
+    pub(crate) fn map_range(&self, range: &Range) -> Range {
+        let (start_row, start_col) =
+            self.map_position(range.start_row, range.start_col);
+        let (end_row, end_col) =
+            self.map_position(range.end_row, range.end_col);
+        Range {
+            start_row,
+            start_col,
+            end_row,
+            end_col,
+        }
+    }
+
+    fn map_position(&self, row: usize, col: usize) -> (usize, usize) {
+        let index = self
+            .snippets
+            .partition_point(|snippet| snippet.ruby_row <= row);
+        let Some(snippet) = index.checked_sub(1).map(|i| &self.snippets[i])
+        else {
+            return (row, col);
+        };
+        let rows_into_snippet = row - snippet.ruby_row;
+        if rows_into_snippet == 0 {
+            (snippet.template_row, snippet.template_col + col)
+        } else {
+            (snippet.template_row + rows_into_snippet, col)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_range() {
+        let mut builder = RubyBuilder::default();
+        builder.push("a", 1, 4);
+        builder.push("if Foo", 3, 4);
+        builder.push("x = 1\n  Bar", 3, 14);
+        builder.push_generated("end");
+        let (ruby, source_map) = builder.build();
+        assert_eq!(ruby, "a\nif Foo\nx = 1\n  Bar\nend");
+
+        // `Foo` is on the first line of its snippet
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 2,
+                start_col: 3,
+                end_row: 2,
+                end_col: 7,
+            }),
+            Range {
+                start_row: 3,
+                start_col: 7,
+                end_row: 3,
+                end_col: 11,
+            }
+        );
+        // `Bar` is on the second line of its snippet, so its column is
+        // unchanged
+        assert_eq!(
+            source_map.map_range(&Range {
+                start_row: 4,
+                start_col: 2,
+                end_row: 4,
+                end_col: 6,
+            }),
+            Range {
+                start_row: 4,
+                start_col: 2,
+                end_row: 4,
+                end_col: 6,
+            }
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use regex::Regex;
+
+use super::{Suppression, UnresolvedReference};
+
+const DIRECTIVE: &str = "pks:disable-next-line";
+
+// Finds `# pks:disable-next-line privacy, dependency -- reason` comments.
+// ERB comments (`<%# pks:disable-next-line privacy %>`) work too.
+pub(crate) fn find_suppressions(contents: &str) -> Vec<Suppression> {
+    let re = Regex::new(&format!(
+        r"#\s*{}\s+([a-z_]+(?:\s*,\s*[a-z_]+)*)\s*(?:--\s*(.*?))?\s*(?:%>)?\s*$",
+        regex::escape(DIRECTIVE)
+    ))
+    .unwrap();
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.contains(DIRECTIVE))
+        .filter_map(|(index, line)| {
+            let captures = re.captures(line)?;
+            let violation_types = captures[1]
+                .split(',')
+                .map(|violation_type| violation_type.trim().to_owned())
+                .collect();
+            let reason = captures
+                .get(2)
+                .map(|reason| reason.as_str().to_owned())
+                .filter(|reason| !reason.is_empty());
+            Some(Suppression {
+                line: index + 1,
+                violation_types,
+                reason,
+            })
+        })
+        .collect()
+}
+
+// Attaches each suppression to the references that start on the next line
+pub(crate) fn attach_suppressions(
+    references: &mut [UnresolvedReference],
+    suppressions: &[Suppression],
+) {
+    if suppressions.is_empty() {
+        return;
+    }
+    for reference in references {
+        reference.suppressions = suppressions
+            .iter()
+            .filter(|suppression| {
+                suppression.line + 1 == reference.location.start_row
+            })
+            .cloned()
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_suppressions() {
+        let contents = "\
+class Foo
+  # pks:disable-next-line privacy -- legacy import, see #123
+  Bar.call
+  # pks:disable-next-line dependency, privacy
+  Baz.call
+  # pks:disable-next-line
+  Qux.call
+end
+";
+        assert_eq!(
+            find_suppressions(contents),
+            vec![
+                Suppression {
+                    line: 2,
+                    violation_types: vec![String::from("privacy")],
+                    reason: Some(String::from("legacy import, see #123")),
+                },
+                Suppression {
+                    line: 4,
+                    violation_types: vec![
+                        String::from("dependency"),
+                        String::from("privacy")
+                    ],
+                    reason: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_suppressions_in_erb() {
+        assert_eq!(
+            find_suppressions("<%# pks:disable-next-line privacy -- ok %>\n"),
+            vec![Suppression {
+                line: 1,
+                violation_types: vec![String::from("privacy")],
+                reason: Some(String::from("ok")),
+            }]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+// Helpers shared by the indentation-based templates (HAML and Slim), which
+// embed Ruby line by line and close blocks by dedenting instead of `end`
+
+use std::path::Path;
+
+use crate::packs::file_utils::file_read_contents;
+use crate::packs::parsing::{
+    process_from_ruby_contents, process_from_ruby_contents_experimental,
+    source_map::{process_template_contents, ConvertToRuby, RubyBuilder},
+};
+use crate::packs::{Configuration, ProcessedFile};
+
+pub(crate) fn process_from_path(
+    path: &Path,
+    configuration: &Configuration,
+    convert_to_ruby: ConvertToRuby,
+) -> anyhow::Result<ProcessedFile> {
+    let contents = file_read_contents(path, configuration)?;
+    Ok(process_from_contents(
+        contents,
+        path,
+        configuration,
+        convert_to_ruby,
+    ))
+}
+
+pub(crate) fn process_from_contents(
+    contents: String,
+    path: &Path,
+    configuration: &Configuration,
+    convert_to_ruby: ConvertToRuby,
+) -> ProcessedFile {
+    process_template_contents(
+        contents,
+        path,
+        configuration,
+        convert_to_ruby,
+        if configuration.experimental_parser {
+            process_from_ruby_contents_experimental
+        } else {
+            process_from_ruby_contents
+        },
+    )
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TemplateLine<'a> {
+    // 1-based
+    pub row: usize,
+    // Byte offset of the first non-indentation character in the template
+    pub start: usize,
+    pub indent: usize,
+    // The line without its indentation and trailing whitespace
+    pub text: &'a str,
+}
+
+pub(crate) fn template_lines(contents: &str) -> Vec<TemplateLine> {
+    let mut line_start = 0;
+    contents
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let text = line.trim_start();
+            let indent = line.len() - text.len();
+            let template_line = TemplateLine {
+                row: index + 1,
+                start: line_start + indent,
+                indent,
+                text: text.trim_end(),
+            };
+            line_start += line.len() + 1;
+            template_line
+        })
+        .collect()
+}
+
+// The index of the first line after `index` that is not nested under a line
+// indented by `indent`. Blank lines count as nested.
+pub(crate) fn end_of_nested_lines(
+    lines: &[TemplateLine],
+    index: usize,
+    indent: usize,
+) -> usize {
+    let mut end = index;
+    while end < lines.len()
+        && (lines[end].text.is_empty() || lines[end].indent > indent)
+    {
+        end += 1;
+    }
+    end
+}
+
+// Ruby code that starts at `code_start` (a byte offset in the template) on
+// `lines[index]`, extended over the following lines while a line ends with
+// one of `continuations` (e.g. a trailing comma in a method call). Returns
+// the code and the index of the line after it.
+pub(crate) fn code_with_continuations<'a>(
+    contents: &'a str,
+    lines: &[TemplateLine],
+    index: usize,
+    code_start: usize,
+    continuations: &[char],
+) -> (&'a str, usize) {
+    let mut last = index;
+    while last + 1 < lines.len() && lines[last].text.ends_with(continuations) {
+        last += 1;
+    }
+    let code_end = lines[last].start + lines[last].text.len();
+    (&contents[code_start..code_end.max(code_start)], last + 1)
+}
+
+// Pushes the Ruby code of every `#{}` interpolation in `text`, a part of the
+// template that starts at `col` on `row`
+pub(crate) fn push_interpolations<'a>(
+    ruby: &mut RubyBuilder<'a>,
+    text: &'a str,
+    row: usize,
+    col: usize,
+) {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+    while let Some(offset) = text[index..].find("#{") {
+        let code_start = index + offset + 2;
+        if index + offset > 0 && bytes[index + offset - 1] == b'\\' {
+            index = code_start;
+            continue;
+        }
+        let mut depth = 1;
+        let mut code_end = code_start;
+        while code_end < bytes.len() {
+            match bytes[code_end] {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            code_end += 1;
+        }
+        if depth != 0 {
+            return;
+        }
+        ruby.push(&text[code_start..code_end], row, col + code_start);
+        index = code_end + 1;
+    }
+}
+
+// The end of a group of attributes (`{...}`, `(...)` or `[...]`) that starts
+// at `start`, skipping over nested groups and quoted strings
+pub(crate) fn end_of_group(text: &str, start: usize) -> usize {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut quote: Option<u8> = None;
+    let mut index = start;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match quote {
+            Some(_) if byte == b'\\' => index += 1,
+            Some(q) if byte == q => quote = None,
+            Some(_) => {}
+            None => match byte {
+                b'"' | b'\'' => quote = Some(byte),
+                b'{' | b'(' | b'[' => depth += 1,
+                b'}' | b')' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return index + 1;
+                    }
+                }
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+    bytes.len()
+}
+
+// Tracks the Ruby blocks opened by template lines so they can be closed with
+// a generated `end` once a line is indented at or before the line that
+// opened them
+#[derive(Debug, Default)]
+pub(crate) struct Blocks {
+    indents: Vec<usize>,
+}
+
+impl Blocks {
+    pub(crate) fn close(
+        &mut self,
+        ruby: &mut RubyBuilder,
+        indent: usize,
+        code: Option<&str>,
+    ) {
+        while let Some(&block_indent) = self.indents.last() {
+            if indent > block_indent
+                || (indent == block_indent
+                    && code.map_or(false, continues_block))
+            {
+                break;
+            }
+            self.indents.pop();
+            ruby.push_generated("end");
+        }
+    }
+
+    pub(crate) fn open(&mut self, indent: usize, code: &str) {
+        if opens_block(code) {
+            self.indents.push(indent);
+        }
+    }
+
+    pub(crate) fn close_all(&mut self, ruby: &mut RubyBuilder) {
+        for _ in self.indents.drain(..) {
+            ruby.push_generated("end");
+        }
+    }
+}
+
+fn opens_block(code: &str) -> bool {
+    let code = code.trim();
+    if ["if", "unless", "case", "while", "until", "for", "begin"]
+        .contains(&first_word(code))
+    {
+        return true;
+    }
+    // `do` or `do |arguments|` at the end of the line
+    let code = match code.strip_suffix('|') {
+        Some(rest) => rest.rfind('|').map_or(rest, |index| &rest[..index]),
+        None => code,
+    };
+    code.trim_end()
+        .strip_suffix("do")
+        .map_or(false, |rest| rest.is_empty() || rest.ends_with(' '))
+}
+
+fn continues_block(code: &str) -> bool {
+    ["else", "elsif", "when", "in", "rescue", "ensure"]
+        .contains(&first_word(code.trim()))
+}
+
+fn first_word(code: &str) -> &str {
+    code.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .next()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_interpolations() {
+        let mut ruby = RubyBuilder::default();
+        push_interpolations(
+            &mut ruby,
+            "Hi #{Foo.name}, \\#{not_code} #{ {a: Bar}[:a] }",
+            2,
+            4,
+        );
+        let (ruby, _) = ruby.build();
+        assert_eq!(ruby, "Foo.name\n {a: Bar}[:a] ");
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert!(opens_block("if Foo.enabled?"));
+        assert!(opens_block("items.each do |item|"));
+        assert!(opens_block("form_for @user do"));
+        assert!(!opens_block("render partial: 'done'"));
+        assert!(!opens_block("notify if Foo.enabled?"));
+        assert!(continues_block("elsif Bar"));
+        assert!(!continues_block("endless"));
+    }
+}
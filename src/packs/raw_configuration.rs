@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     fs::File,
     path::{Path, PathBuf},
@@ -10,6 +10,10 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 
+use crate::packs::pack::{
+    deserialize_checker_setting, serialize_checker_setting, CheckerSetting,
+};
+
 const CONFIG_FILE_NAME: &str = "packwerk.yml";
 const PACKS_FIRST_CONFIG_FILE_NAME: &str = "packs.yml";
 
@@ -50,7 +54,21 @@ pub(crate) struct RawConfiguration {
 
     // Architecture layers
     #[serde(default)]
-    pub layers: Vec<String>,
+    pub layers: RawLayers,
+
+    // Globs relative to each pack matching its test files, which may also
+    // depend on the pack's `dev_dependencies`
+    #[serde(default = "default_test_paths")]
+    pub test_paths: Vec<String>,
+
+    // Dependencies between packs that are never allowed
+    #[serde(default)]
+    pub forbidden_dependencies: Vec<RawForbiddenDependency>,
+
+    // Stop `update` from adding entries to `package_todo.yml` files, so they
+    // can only shrink
+    #[serde(default)]
+    pub todo_ratchet: bool,
 
     // Experimental parser
     #[serde(default)]
@@ -116,6 +134,40 @@ fn get_from_file_that_exists(
     Ok(configuration)
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub(crate) enum RawLayers {
+    // From the highest layer to the lowest. Each layer may reference the
+    // layers below it.
+    Ordered(Vec<String>),
+    // Each layer and the other layers it may reference
+    Graph(BTreeMap<String, Option<Vec<String>>>),
+}
+
+impl Default for RawLayers {
+    fn default() -> Self {
+        RawLayers::Ordered(vec![])
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub(crate) struct RawForbiddenDependency {
+    // Globs matching the names of the depending and the depended on packs
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub reason: String,
+    // True when unset. Strict violations can't be recorded in
+    // `package_todo.yml`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_checker_setting",
+        deserialize_with = "deserialize_checker_setting"
+    )]
+    pub enforce: Option<CheckerSetting>,
+}
+
 // Normally if a key is not set, serde will use the default value for that type.
 // If there is no `packwerk.yml` at all, we use `RawConfiguration::default()` to get the default,
 // So this implementation of default ensures that the default is the same as the serde default.
@@ -133,6 +185,8 @@ fn default_include() -> Vec<String> {
         String::from("**/*.rb"),
         String::from("**/*.rake"),
         String::from("**/*.erb"),
+        String::from("**/*.haml"),
+        String::from("**/*.slim"),
     ]
 }
 
@@ -148,6 +202,10 @@ fn default_custom_associations() -> Vec<String> {
     vec![]
 }
 
+fn default_test_paths() -> Vec<String> {
+    vec![String::from("spec/**"), String::from("test/**")]
+}
+
 fn default_cache() -> bool {
     true
 }
@@ -210,4 +268,35 @@ mod tests {
 
         assert_eq!(raw_configuration.package_paths, vec!["**/*"]);
     }
+
+    #[test]
+    fn test_deserialize_layers() {
+        let raw_configuration = serde_yaml::from_str::<RawConfiguration>(
+            "layers:\n  - product\n  - utilities",
+        )
+        .unwrap();
+        assert_eq!(
+            raw_configuration.layers,
+            RawLayers::Ordered(vec![
+                String::from("product"),
+                String::from("utilities")
+            ])
+        );
+
+        let raw_configuration = serde_yaml::from_str::<RawConfiguration>(
+            "layers:\n  ui: [domain]\n  domain: [persistence]\n  persistence:",
+        )
+        .unwrap();
+        assert_eq!(
+            raw_configuration.layers,
+            RawLayers::Graph(BTreeMap::from([
+                (String::from("ui"), Some(vec![String::from("domain")])),
+                (
+                    String::from("domain"),
+                    Some(vec![String::from("persistence")])
+                ),
+                (String::from("persistence"), None),
+            ]))
+        );
+    }
 }
@@ -4,8 +4,9 @@ use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use tracing::debug;
 
 use crate::packs::{
-    get_experimental_constant_resolver, get_zeitwerk_constant_resolver,
-    process_files_with_cache, ProcessedFile,
+    constant_resolver::ConstantResolver, get_experimental_constant_resolver,
+    get_zeitwerk_constant_resolver, process_files_with_cache, ProcessedFile,
+    Suppression,
 };
 
 use super::{checker::reference::Reference, Configuration};
@@ -14,6 +15,20 @@ pub(crate) fn get_all_references(
     configuration: &Configuration,
     absolute_paths: &HashSet<PathBuf>,
 ) -> anyhow::Result<Vec<Reference>> {
+    let (references, _) =
+        get_all_references_and_suppressions(configuration, absolute_paths)?;
+    Ok(references)
+}
+
+// A `pks:disable-next-line` comment and the path of its file relative to the
+// root
+pub(crate) type FileSuppression = (String, Suppression);
+
+// Also returns every `pks:disable-next-line` comment in the processed files
+pub(crate) fn get_all_references_and_suppressions(
+    configuration: &Configuration,
+    absolute_paths: &HashSet<PathBuf>,
+) -> anyhow::Result<(Vec<Reference>, Vec<FileSuppression>)> {
     let cache = configuration.get_cache();
 
     debug!("Getting unresolved references (using cache if possible)");
@@ -63,15 +78,12 @@ pub(crate) fn get_all_references(
             // Start with an empty vector for each thread
             |mut acc, processed_file| {
                 // Try to fold results within a thread
-                for unresolved_ref in &processed_file.unresolved_references {
-                    let mut refs = Reference::from_unresolved_reference(
-                        configuration,
-                        constant_resolver.as_ref(),
-                        unresolved_ref,
-                        &processed_file.absolute_path,
-                    )?;
-                    acc.append(&mut refs); // Collect references, return error if any
-                }
+                let mut refs = get_references_for_processed_file(
+                    configuration,
+                    constant_resolver.as_ref(),
+                    processed_file,
+                )?;
+                acc.append(&mut refs); // Collect references, return error if any
                 Ok(acc)
             },
         )
@@ -85,5 +97,62 @@ pub(crate) fn get_all_references(
         );
     debug!("Finished turning unresolved references into fully qualified references");
 
-    references
+    let suppressions = processed_files_to_check
+        .iter()
+        .flat_map(|processed_file| {
+            let relative_path = processed_file
+                .absolute_path
+                .strip_prefix(&configuration.absolute_root)
+                .unwrap_or(&processed_file.absolute_path)
+                .to_string_lossy()
+                .to_string();
+            processed_file.suppressions.iter().map(move |suppression| {
+                (relative_path.clone(), suppression.clone())
+            })
+        })
+        .collect();
+
+    Ok((references?, suppressions))
+}
+
+pub(crate) fn get_references_for_processed_file(
+    configuration: &Configuration,
+    constant_resolver: &(dyn ConstantResolver + Send + Sync),
+    processed_file: &ProcessedFile,
+) -> anyhow::Result<Vec<Reference>> {
+    let mut references = Vec::new();
+    for unresolved_ref in &processed_file.unresolved_references {
+        let mut refs = Reference::from_unresolved_reference(
+            configuration,
+            constant_resolver,
+            unresolved_ref,
+            &processed_file.absolute_path,
+        )?;
+        references.append(&mut refs);
+    }
+    Ok(references)
+}
+
+// Builds the constant resolver used by `get_all_references`, so that it can be
+// kept around and reused (e.g. by the language server)
+pub(crate) fn get_constant_resolver(
+    configuration: &Configuration,
+) -> anyhow::Result<Box<dyn ConstantResolver + Send + Sync>> {
+    if configuration.experimental_parser {
+        let all_processed_files: Vec<ProcessedFile> = process_files_with_cache(
+            &configuration.included_files,
+            configuration.get_cache(),
+            configuration,
+        )?;
+        Ok(get_experimental_constant_resolver(
+            &configuration.absolute_root,
+            &all_processed_files,
+            &configuration.ignored_definitions,
+        ))
+    } else {
+        Ok(get_zeitwerk_constant_resolver(
+            &configuration.pack_set,
+            &configuration.constant_resolver_configuration(),
+        ))
+    }
 }
@@ -0,0 +1,486 @@
+// `packs server` keeps a project loaded and answers requests on a Unix
+// domain socket, so `check` doesn't pay for walking the project and building
+// the constant resolver each time. Requests and responses are one line of
+// JSON each, one request per connection:
+//
+//   {"method": "check", "files": ["packs/foo/app/services/foo.rb"]}
+//   {"method": "check_contents", "file": "...", "contents": "..."}
+//   {"method": "list_packs"}
+//   {"method": "resolve_constant", "constant": "Bar", "namespace": ["Foo"]}
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, MetadataExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use anyhow::{bail, Context};
+use notify::{Event, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::checker::reporter::{get_reporter, OutputFormat};
+use super::checker::{check_result_for_violations, CheckAllResult};
+use super::watch::WatchedProject;
+use super::Configuration;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub(crate) enum Request {
+    Check {
+        // Every included file when empty
+        #[serde(default)]
+        files: Vec<String>,
+        #[serde(flatten)]
+        options: CheckOptions,
+    },
+    CheckContents {
+        file: String,
+        contents: String,
+        #[serde(flatten)]
+        options: CheckOptions,
+    },
+    ListPacks,
+    ResolveConstant {
+        constant: String,
+        // The modules the constant is referenced in, outermost first
+        #[serde(default)]
+        namespace: Vec<String>,
+    },
+}
+
+// The options of `check` that only change how violations are reported
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub(crate) struct CheckOptions {
+    pub(crate) ignore_recorded_violations: bool,
+    pub(crate) format: OutputFormat,
+    pub(crate) color: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+enum Response {
+    Check {
+        // What `check` would print
+        output: String,
+        has_violations: bool,
+    },
+    Packs {
+        packs: Vec<PackSummary>,
+    },
+    Constant {
+        definitions: Vec<ResolvedConstant>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PackSummary {
+    name: String,
+    yml: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ResolvedConstant {
+    fully_qualified_name: String,
+    // Relative to the project root
+    path: PathBuf,
+    pack: Option<String>,
+}
+
+// The options that change which violations are found. Servers started with
+// different settings listen on different sockets, so a client only uses a
+// server that finds the same violations it would.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) experimental_parser: bool,
+    pub(crate) disable_enforce_dependencies: bool,
+    pub(crate) disable_enforce_folder_privacy: bool,
+    pub(crate) disable_enforce_layers: bool,
+    pub(crate) disable_enforce_privacy: bool,
+    pub(crate) disable_enforce_visibility: bool,
+}
+
+impl Settings {
+    fn of(configuration: &Configuration) -> Settings {
+        Settings {
+            experimental_parser: configuration.experimental_parser,
+            disable_enforce_dependencies: configuration
+                .disable_enforce_dependencies,
+            disable_enforce_folder_privacy: configuration
+                .disable_enforce_folder_privacy,
+            disable_enforce_layers: configuration.disable_enforce_layers,
+            disable_enforce_privacy: configuration.disable_enforce_privacy,
+            disable_enforce_visibility: configuration
+                .disable_enforce_visibility,
+        }
+    }
+
+    // Socket paths are limited to about 100 bytes, so they can't live under
+    // the project root
+    pub(crate) fn socket_path(&self, absolute_root: &Path) -> PathBuf {
+        let key = format!(
+            "{}\n{}",
+            absolute_root.display(),
+            serde_json::to_string(self).unwrap_or_default()
+        );
+        socket_dir().join(format!("{:x}.sock", md5::compute(key)))
+    }
+}
+
+// Only the current user may access the directory of their sockets, since
+// clients trust whatever answers on them
+fn socket_dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(runtime_dir) if !runtime_dir.is_empty() => {
+            PathBuf::from(runtime_dir).join("pks")
+        }
+        _ => std::env::temp_dir().join(format!("pks-{}", current_uid())),
+    }
+}
+
+fn current_uid() -> u32 {
+    // SAFETY: geteuid has no preconditions and can't fail
+    unsafe { libc::geteuid() }
+}
+
+// Whether `path` belongs to the current user and, unless it's a socket, no
+// one else can access it. Symlinks aren't followed, since another user could
+// point them anywhere.
+fn is_private(path: &Path) -> bool {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) => {
+            metadata.uid() == current_uid()
+                && (metadata.file_type().is_socket()
+                    || metadata.mode() & 0o077 == 0)
+        }
+        Err(_) => false,
+    }
+}
+
+fn create_socket_dir(dir: &Path) -> anyhow::Result<()> {
+    match fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+            if !is_private(dir) {
+                bail!(
+                    "{} must be a directory owned by the current user that no one else can access",
+                    dir.display()
+                );
+            }
+            Ok(())
+        }
+        Err(error) => {
+            Err(error).context(format!("Failed to create {}", dir.display()))
+        }
+    }
+}
+
+pub(crate) fn run(configuration: Configuration) -> anyhow::Result<()> {
+    let absolute_root = configuration.absolute_root.clone();
+    let socket_path = Settings::of(&configuration).socket_path(&absolute_root);
+    create_socket_dir(socket_path.parent().unwrap())?;
+    if UnixStream::connect(&socket_path).is_ok() {
+        bail!("A server is already listening on {}", socket_path.display());
+    }
+    // Left behind by a server that didn't shut down cleanly
+    let _ = fs::remove_file(&socket_path);
+
+    let mut project = WatchedProject::new(configuration)?;
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(&absolute_root, RecursiveMode::Recursive)?;
+
+    let listener = UnixListener::bind(&socket_path)
+        .context(format!("Failed to listen on {}", socket_path.display()))?;
+    println!("Listening on {}", socket_path.display());
+
+    for stream in listener.incoming() {
+        // Files changed since the last request
+        let events: Vec<Event> = receiver.try_iter().flatten().collect();
+        if let Err(error) = project.update(&events) {
+            // Keep answering until the files are fixed
+            println!("Error: {:#}", error);
+        }
+        let result = stream
+            .map_err(anyhow::Error::from)
+            .and_then(|stream| handle_connection(&mut project, stream));
+        if let Err(error) = result {
+            debug!("Failed to handle connection: {:?}", error);
+        }
+    }
+    Ok(())
+}
+
+fn handle_connection(
+    project: &mut WatchedProject,
+    mut stream: UnixStream,
+) -> anyhow::Result<()> {
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    debug!("Received {}", line.trim_end());
+    let response = serde_json::from_str(&line)
+        .map_err(anyhow::Error::from)
+        .and_then(|request| handle_request(project, request))
+        .unwrap_or_else(|error| Response::Error {
+            message: format!("{:#}", error),
+        });
+    serde_json::to_writer(&mut stream, &response)?;
+    stream.write_all(b"\n")?;
+    Ok(())
+}
+
+fn handle_request(
+    project: &mut WatchedProject,
+    request: Request,
+) -> anyhow::Result<Response> {
+    match request {
+        Request::Check { files, options } => {
+            let absolute_paths =
+                project.configuration.intersect_files(files.clone());
+            // Named files may have been saved a moment ago, before their
+            // events arrived
+            if !files.is_empty() {
+                project.refresh_files(absolute_paths.clone())?;
+            }
+            set_check_options(&mut project.configuration, options);
+            let result = project.result_for_files(absolute_paths)?;
+            check_response(&project.configuration, &result)
+        }
+        Request::CheckContents {
+            file,
+            contents,
+            options,
+        } => {
+            let absolute_paths =
+                project.configuration.intersect_files(vec![file]);
+            let violations = match absolute_paths.iter().next() {
+                Some(path) => project.check_contents(path, contents)?,
+                None => HashSet::new(),
+            };
+            set_check_options(&mut project.configuration, options);
+            let result = check_result_for_violations(
+                &project.configuration,
+                absolute_paths,
+                violations,
+            )?;
+            check_response(&project.configuration, &result)
+        }
+        Request::ListPacks => Ok(Response::Packs {
+            packs: project
+                .configuration
+                .pack_set
+                .packs
+                .iter()
+                .map(|pack| PackSummary {
+                    name: pack.name.clone(),
+                    yml: pack.yml.clone(),
+                })
+                .collect(),
+        }),
+        Request::ResolveConstant {
+            constant,
+            namespace,
+        } => {
+            let namespace_path: Vec<&str> =
+                namespace.iter().map(String::as_str).collect();
+            let configuration = &project.configuration;
+            let definitions = project
+                .resolve(&constant, &namespace_path)
+                .into_iter()
+                .map(|definition| {
+                    let path = definition.absolute_path_of_definition;
+                    ResolvedConstant {
+                        fully_qualified_name: definition.fully_qualified_name,
+                        pack: configuration
+                            .pack_set
+                            .for_file(&path)
+                            .ok()
+                            .flatten()
+                            .map(|pack| pack.name.clone()),
+                        path: path
+                            .strip_prefix(&configuration.absolute_root)
+                            .map(Path::to_path_buf)
+                            .unwrap_or(path),
+                    }
+                })
+                .collect();
+            Ok(Response::Constant { definitions })
+        }
+    }
+}
+
+fn set_check_options(configuration: &mut Configuration, options: CheckOptions) {
+    configuration.ignore_recorded_violations =
+        options.ignore_recorded_violations;
+    configuration.output_format = options.format;
+    configuration.color = options.color;
+}
+
+fn check_response(
+    configuration: &Configuration,
+    result: &CheckAllResult,
+) -> anyhow::Result<Response> {
+    Ok(Response::Check {
+        output: get_reporter(configuration)?.report_check(result)?,
+        has_violations: result.has_violations(),
+    })
+}
+
+pub(crate) struct Client {
+    stream: UnixStream,
+}
+
+impl Client {
+    // `None` when no server is listening for this project and settings
+    pub(crate) fn connect(
+        absolute_root: &Path,
+        settings: &Settings,
+    ) -> Option<Client> {
+        let socket_path = settings.socket_path(absolute_root);
+        if !socket_path.exists() {
+            return None;
+        }
+        // Another user could be listening on it, and answer that there are
+        // no violations
+        if !(is_private(socket_path.parent()?) && is_private(&socket_path)) {
+            debug!(
+                "Not using {}, other users can access it",
+                socket_path.display()
+            );
+            return None;
+        }
+        let stream = UnixStream::connect(&socket_path).ok()?;
+        debug!("Using the server listening on {}", socket_path.display());
+        Some(Client { stream })
+    }
+
+    pub(crate) fn check(
+        self,
+        files: Vec<String>,
+        options: CheckOptions,
+    ) -> anyhow::Result<()> {
+        self.check_request(&Request::Check { files, options })
+    }
+
+    pub(crate) fn check_contents(
+        self,
+        file: String,
+        options: CheckOptions,
+    ) -> anyhow::Result<()> {
+        let contents = io::read_to_string(io::stdin()).context(format!(
+            "Failed to read contents of {} from stdin",
+            file
+        ))?;
+        self.check_request(&Request::CheckContents {
+            file,
+            contents,
+            options,
+        })
+    }
+
+    // Prints what the server found the way `check` does
+    fn check_request(self, request: &Request) -> anyhow::Result<()> {
+        match self.send(request)? {
+            Response::Check {
+                output,
+                has_violations,
+            } => {
+                if !output.is_empty() {
+                    println!("{}", output);
+                }
+                if has_violations {
+                    bail!("Violations found!")
+                }
+                Ok(())
+            }
+            Response::Error { message } => bail!(message),
+            response => {
+                bail!("Unexpected response from server: {:?}", response)
+            }
+        }
+    }
+
+    fn send(mut self, request: &Request) -> anyhow::Result<Response> {
+        serde_json::to_writer(&mut self.stream, request)?;
+        self.stream.write_all(b"\n")?;
+        let mut line = String::new();
+        BufReader::new(&self.stream).read_line(&mut line)?;
+        serde_json::from_str(&line).context("Invalid response from server")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_requests() {
+        let request: Request = serde_json::from_str(
+            r#"{"method": "check", "files": ["a.rb"], "format": "json"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            Request::Check {
+                files: vec![String::from("a.rb")],
+                options: CheckOptions {
+                    format: OutputFormat::Json,
+                    ..CheckOptions::default()
+                },
+            }
+        );
+
+        let request: Request = serde_json::from_str(
+            r#"{"method": "resolve_constant", "constant": "Bar"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            Request::ResolveConstant {
+                constant: String::from("Bar"),
+                namespace: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_is_private() {
+        let dir = std::env::temp_dir().join("packs_server_is_private");
+        let _ = fs::remove_dir_all(&dir);
+        fs::DirBuilder::new().mode(0o700).create(&dir).unwrap();
+        assert!(is_private(&dir));
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(!is_private(&dir));
+        assert!(create_socket_dir(&dir).is_err());
+        assert!(!is_private(&dir.join("missing.sock")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_socket_path() {
+        let root = Path::new("/app");
+        let experimental = Settings {
+            experimental_parser: true,
+            ..Settings::default()
+        };
+        assert_eq!(
+            Settings::default().socket_path(root),
+            Settings::default().socket_path(root)
+        );
+        assert_ne!(
+            Settings::default().socket_path(root),
+            experimental.socket_path(root)
+        );
+        assert_ne!(
+            Settings::default().socket_path(root),
+            Settings::default().socket_path(Path::new("/other_app"))
+        );
+    }
+}
@@ -0,0 +1,279 @@
+// Counts of the violations recorded in package_todo.yml files, to track how
+// modularization progresses.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{git, Configuration, PackageTodo};
+
+const UNOWNED: &str = "unowned";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatsFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+struct Stats {
+    total: usize,
+    // Keyed by the referencing pack, whose package_todo.yml records them
+    by_pack: BTreeMap<String, usize>,
+    by_owner: BTreeMap<String, usize>,
+    by_violation_type: BTreeMap<String, usize>,
+    by_defining_pack: BTreeMap<String, usize>,
+    // Oldest commit first, only with `--git-history`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    history: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct HistoryEntry {
+    commit: String,
+    date: String,
+    total: usize,
+    by_pack: BTreeMap<String, usize>,
+}
+
+pub(crate) fn stats(
+    configuration: &Configuration,
+    format: StatsFormat,
+    git_history: Option<usize>,
+) -> anyhow::Result<String> {
+    let mut stats = current_stats(configuration);
+    if let Some(commit_count) = git_history {
+        stats.history = history(&configuration.absolute_root, commit_count)?;
+    }
+
+    match format {
+        StatsFormat::Text => stats.to_text(),
+        StatsFormat::Json => Ok(serde_json::to_string_pretty(&stats)?),
+    }
+}
+
+fn current_stats(configuration: &Configuration) -> Stats {
+    let pack_set = &configuration.pack_set;
+    let mut stats = Stats::default();
+    for identifier in &pack_set.all_violations {
+        let owner = pack_set
+            .for_pack(&identifier.referencing_pack_name)
+            .ok()
+            .and_then(|pack| pack.owner.clone())
+            .unwrap_or_else(|| String::from(UNOWNED));
+
+        stats.total += 1;
+        *stats
+            .by_pack
+            .entry(identifier.referencing_pack_name.clone())
+            .or_default() += 1;
+        *stats.by_owner.entry(owner).or_default() += 1;
+        *stats
+            .by_violation_type
+            .entry(identifier.violation_type.clone())
+            .or_default() += 1;
+        *stats
+            .by_defining_pack
+            .entry(identifier.defining_pack_name.clone())
+            .or_default() += 1;
+    }
+    stats
+}
+
+fn history(
+    absolute_root: &Path,
+    commit_count: usize,
+) -> anyhow::Result<Vec<HistoryEntry>> {
+    let mut commits = git::recent_commits(absolute_root, commit_count)?;
+    commits.reverse();
+
+    let mut history = vec![];
+    for commit in commits {
+        let package_todo_files: Vec<String> =
+            git::files_at_commit(absolute_root, &commit.sha)?
+                .into_iter()
+                .filter(|file| {
+                    Path::new(file).file_name()
+                        == Some("package_todo.yml".as_ref())
+                })
+                .collect();
+        let contents = git::read_files_at_commit(
+            absolute_root,
+            &commit.sha,
+            &package_todo_files,
+        )?;
+
+        let mut by_pack: BTreeMap<String, usize> = BTreeMap::new();
+        for (file, contents) in package_todo_files.iter().zip(contents) {
+            // Files that don't parse (e.g. with merge conflicts) don't count
+            let Some(package_todo) = contents.and_then(|contents| {
+                serde_yaml::from_str::<PackageTodo>(&contents).ok()
+            }) else {
+                continue;
+            };
+            let violation_count = package_todo.violation_count();
+            if violation_count > 0 {
+                *by_pack.entry(pack_name_for_todo(file)).or_default() +=
+                    violation_count;
+            }
+        }
+        history.push(HistoryEntry {
+            commit: commit.sha,
+            date: commit.date,
+            total: by_pack.values().sum(),
+            by_pack,
+        });
+    }
+    Ok(history)
+}
+
+// The pack a package_todo.yml file belongs to, by the folder it's in
+fn pack_name_for_todo(file: &str) -> String {
+    match Path::new(file).parent() {
+        Some(folder) if !folder.as_os_str().is_empty() => {
+            folder.to_string_lossy().to_string()
+        }
+        _ => String::from("."),
+    }
+}
+
+impl Stats {
+    fn to_text(&self) -> anyhow::Result<String> {
+        let mut output = String::new();
+        writeln!(output, "{} recorded violation(s)", self.total)?;
+        for (heading, counts) in [
+            ("Pack", &self.by_pack),
+            ("Owner", &self.by_owner),
+            ("Violation type", &self.by_violation_type),
+            ("Defining pack", &self.by_defining_pack),
+        ] {
+            if counts.is_empty() {
+                continue;
+            }
+            // Most violations first
+            let mut counts: Vec<(&String, &usize)> = counts.iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            let rows = counts
+                .into_iter()
+                .map(|(name, count)| vec![name.clone(), count.to_string()])
+                .collect();
+            writeln!(output)?;
+            write_table(&mut output, vec![heading, "Violations"], rows)?;
+        }
+
+        if !self.history.is_empty() {
+            let mut pack_names: Vec<&String> = self
+                .history
+                .iter()
+                .flat_map(|entry| entry.by_pack.keys())
+                .collect();
+            pack_names.sort();
+            pack_names.dedup();
+
+            let mut headers = vec!["Commit", "Date", "Total"];
+            headers.extend(pack_names.iter().map(|name| name.as_str()));
+            let rows = self
+                .history
+                .iter()
+                .map(|entry| {
+                    let mut row = vec![
+                        entry.commit.chars().take(7).collect(),
+                        entry.date.clone(),
+                        entry.total.to_string(),
+                    ];
+                    row.extend(pack_names.iter().map(|name| {
+                        entry.by_pack.get(*name).unwrap_or(&0).to_string()
+                    }));
+                    row
+                })
+                .collect();
+            writeln!(output)?;
+            write_table(&mut output, headers, rows)?;
+        }
+        Ok(output.trim_end().to_owned())
+    }
+}
+
+// Left-aligned columns, two spaces apart
+fn write_table(
+    output: &mut String,
+    headers: Vec<&str>,
+    rows: Vec<Vec<String>>,
+) -> std::fmt::Result {
+    let mut widths: Vec<usize> =
+        headers.iter().map(|header| header.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let headers = headers.into_iter().map(String::from).collect();
+    for row in std::iter::once(headers).chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(output, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_name_for_todo() {
+        assert_eq!(
+            pack_name_for_todo("packs/foo/package_todo.yml"),
+            "packs/foo"
+        );
+        assert_eq!(pack_name_for_todo("package_todo.yml"), ".");
+    }
+
+    #[test]
+    fn test_to_text() {
+        let stats = Stats {
+            total: 3,
+            by_pack: BTreeMap::from([(String::from("packs/foo"), 3)]),
+            by_owner: BTreeMap::from([(String::from("unowned"), 3)]),
+            by_violation_type: BTreeMap::from([
+                (String::from("dependency"), 1),
+                (String::from("privacy"), 2),
+            ]),
+            by_defining_pack: BTreeMap::from([(String::from("packs/bar"), 3)]),
+            history: vec![HistoryEntry {
+                commit: String::from("0123456789abcdef"),
+                date: String::from("2024-01-31T12:00:00+00:00"),
+                total: 3,
+                by_pack: BTreeMap::from([(String::from("packs/foo"), 3)]),
+            }],
+        };
+
+        assert_eq!(
+            stats.to_text().unwrap(),
+            "\
+3 recorded violation(s)
+
+Pack       Violations
+packs/foo  3
+
+Owner    Violations
+unowned  3
+
+Violation type  Violations
+privacy         2
+dependency      1
+
+Defining pack  Violations
+packs/bar      3
+
+Commit   Date                       Total  packs/foo
+0123456  2024-01-31T12:00:00+00:00  3      3"
+        );
+    }
+}
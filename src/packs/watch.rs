@@ -0,0 +1,358 @@
+// `check --watch` (and `server`) keep the configuration, the constant
+// resolver and the violations of every file in memory, and re-check only
+// what changed.
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::bail;
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use tracing::debug;
+
+use super::checker::reporter::{get_reporter, text, OutputFormat};
+use super::checker::{
+    check_references, check_result_for_violations, get_checkers,
+    CheckAllResult, CheckerInterface, ViolationIdentifier,
+};
+use super::configuration::CONFIGURATION_FILE_NAMES;
+use super::constant_resolver::{ConstantDefinition, ConstantResolver};
+use super::file_utils::get_file_type;
+use super::parsing::process_file_contents;
+use super::reference_extractor::get_references_for_processed_file;
+use super::{
+    get_experimental_constant_resolver, get_zeitwerk_constant_resolver,
+    process_files_with_cache, Configuration, ProcessedFile, Violation,
+};
+
+// Saving a file often produces several events, so events that arrive within
+// this long of each other are handled together
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+pub(crate) struct WatchedProject {
+    pub(crate) configuration: Configuration,
+    constant_resolver: Box<dyn ConstantResolver + Send + Sync>,
+    checkers: Vec<Box<dyn CheckerInterface + Send + Sync>>,
+    processed_files: HashMap<PathBuf, ProcessedFile>,
+    // Keyed by the absolute path of the referencing file
+    violations: HashMap<PathBuf, HashSet<Violation>>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Changes {
+    reload_configuration: bool,
+    rebuild_constant_resolver: bool,
+    // Files to process again
+    files: HashSet<PathBuf>,
+}
+
+pub(crate) fn watch(configuration: Configuration) -> anyhow::Result<()> {
+    if configuration.output_format != OutputFormat::Text {
+        bail!(
+            "`--format {}` is not supported by `check --watch`",
+            configuration.output_format.name()
+        );
+    }
+
+    let absolute_root = configuration.absolute_root.clone();
+    let mut project = WatchedProject::new(configuration)?;
+    let result = project.result()?;
+    println!(
+        "{}",
+        get_reporter(&project.configuration)?.report_check(&result)?
+    );
+    let mut reportable_violations = result.reportable_violations().clone();
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(&absolute_root, RecursiveMode::Recursive)?;
+    println!("Watching for changes...");
+
+    while let Ok(event) = receiver.recv() {
+        let mut events = vec![event];
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            events.push(event);
+        }
+        let events: Vec<Event> = events.into_iter().flatten().collect();
+        match project.update(&events) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            // Keep watching if a file is broken halfway through an edit
+            Err(error) => {
+                println!("Error: {:#}", error);
+                continue;
+            }
+        }
+        let result = project.result()?;
+        let (new_violations, fixed_violations) = violation_delta(
+            &reportable_violations,
+            result.reportable_violations(),
+        );
+        let reporter = text::Reporter {
+            color: project.configuration.color,
+        };
+        println!(
+            "{}",
+            reporter.report_delta(new_violations, fixed_violations)?
+        );
+        reportable_violations = result.reportable_violations().clone();
+    }
+
+    Ok(())
+}
+
+impl WatchedProject {
+    pub(crate) fn new(
+        configuration: Configuration,
+    ) -> anyhow::Result<WatchedProject> {
+        let processed_files: HashMap<PathBuf, ProcessedFile> =
+            process_files_with_cache(
+                &configuration.included_files,
+                configuration.get_cache(),
+                &configuration,
+            )?
+            .into_iter()
+            .map(|processed_file| {
+                (processed_file.absolute_path.clone(), processed_file)
+            })
+            .collect();
+        let mut project = WatchedProject {
+            constant_resolver: build_constant_resolver(
+                &configuration,
+                &processed_files,
+            ),
+            checkers: get_checkers(&configuration),
+            configuration,
+            processed_files,
+            violations: HashMap::new(),
+        };
+        let files = project.processed_files.keys().cloned().collect();
+        project.check_files(&files)?;
+        Ok(project)
+    }
+
+    // Catches up with file system events. Returns whether anything changed.
+    pub(crate) fn update(&mut self, events: &[Event]) -> anyhow::Result<bool> {
+        let changes = self.changes(events);
+        if changes == Changes::default() {
+            return Ok(false);
+        }
+        self.apply(changes)?;
+        Ok(true)
+    }
+
+    // Reads files again without waiting for their events, e.g. right before
+    // checking them
+    pub(crate) fn refresh_files(
+        &mut self,
+        files: HashSet<PathBuf>,
+    ) -> anyhow::Result<()> {
+        self.apply(Changes {
+            files,
+            ..Changes::default()
+        })
+    }
+
+    fn changes(&self, events: &[Event]) -> Changes {
+        let mut changes = Changes::default();
+        for event in events {
+            let structural = match event.kind {
+                EventKind::Create(_)
+                | EventKind::Remove(_)
+                | EventKind::Modify(ModifyKind::Name(_)) => true,
+                EventKind::Modify(ModifyKind::Metadata(_)) => continue,
+                EventKind::Modify(_) => false,
+                _ => continue,
+            };
+            for path in &event.paths {
+                if path.starts_with(&self.configuration.cache_directory) {
+                    continue;
+                }
+                let file_name = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or_default();
+                if CONFIGURATION_FILE_NAMES.contains(&file_name) {
+                    changes.reload_configuration = true;
+                    // Recorded violations don't affect constant resolution
+                    if file_name != "package_todo.yml" {
+                        changes.rebuild_constant_resolver = true;
+                    }
+                } else if get_file_type(path).is_none() {
+                    continue;
+                } else if structural {
+                    // Adding or removing a file changes the included files
+                    // and the constants Zeitwerk can find
+                    changes.reload_configuration = true;
+                    changes.rebuild_constant_resolver = true;
+                    changes.files.insert(path.clone());
+                } else if self.configuration.included_files.contains(path) {
+                    changes.files.insert(path.clone());
+                    // The experimental parser finds constants in the files
+                    changes.rebuild_constant_resolver |=
+                        self.configuration.experimental_parser;
+                }
+            }
+        }
+        changes
+    }
+
+    fn apply(&mut self, changes: Changes) -> anyhow::Result<()> {
+        if changes.reload_configuration {
+            debug!("Reloading configuration");
+            self.configuration = self.configuration.reload()?;
+            self.checkers = get_checkers(&self.configuration);
+        }
+        let included_files = &self.configuration.included_files;
+        self.processed_files
+            .retain(|path, _| included_files.contains(path));
+        self.violations
+            .retain(|path, _| included_files.contains(path));
+
+        let files: HashSet<PathBuf> = changes
+            .files
+            .into_iter()
+            .filter(|path| included_files.contains(path))
+            .collect();
+        debug!("Processing {} changed file(s)", files.len());
+        for processed_file in process_files_with_cache(
+            &files,
+            self.configuration.get_cache(),
+            &self.configuration,
+        )? {
+            self.processed_files
+                .insert(processed_file.absolute_path.clone(), processed_file);
+        }
+
+        if changes.rebuild_constant_resolver {
+            debug!("Rebuilding constant resolver");
+            self.constant_resolver = build_constant_resolver(
+                &self.configuration,
+                &self.processed_files,
+            );
+        }
+
+        // Any reference may resolve or be checked differently once the
+        // configuration or the constants change
+        let files_to_check = if changes.reload_configuration
+            || changes.rebuild_constant_resolver
+        {
+            self.processed_files.keys().cloned().collect()
+        } else {
+            files
+        };
+        self.check_files(&files_to_check)
+    }
+
+    fn check_files(&mut self, files: &HashSet<PathBuf>) -> anyhow::Result<()> {
+        let violations = files
+            .par_iter()
+            .filter_map(|path| self.processed_files.get(path))
+            .map(|processed_file| {
+                let references = get_references_for_processed_file(
+                    &self.configuration,
+                    self.constant_resolver.as_ref(),
+                    processed_file,
+                )?;
+                let violations = check_references(
+                    &self.configuration,
+                    &references,
+                    &self.checkers,
+                )?;
+                Ok((processed_file.absolute_path.clone(), violations))
+            })
+            .collect::<anyhow::Result<Vec<(PathBuf, HashSet<Violation>)>>>()?;
+        self.violations.extend(violations);
+        Ok(())
+    }
+
+    // Violations of contents that aren't saved, e.g. an editor buffer
+    pub(crate) fn check_contents(
+        &self,
+        path: &Path,
+        contents: String,
+    ) -> anyhow::Result<HashSet<Violation>> {
+        let processed_file =
+            process_file_contents(path, contents, &self.configuration);
+        let references = get_references_for_processed_file(
+            &self.configuration,
+            self.constant_resolver.as_ref(),
+            &processed_file,
+        )?;
+        check_references(&self.configuration, &references, &self.checkers)
+    }
+
+    pub(crate) fn resolve(
+        &self,
+        constant: &str,
+        namespace_path: &[&str],
+    ) -> Vec<ConstantDefinition> {
+        self.constant_resolver
+            .resolve(constant, namespace_path)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn result(&self) -> anyhow::Result<CheckAllResult> {
+        self.result_for_files(self.processed_files.keys().cloned().collect())
+    }
+
+    pub(crate) fn result_for_files(
+        &self,
+        files: HashSet<PathBuf>,
+    ) -> anyhow::Result<CheckAllResult> {
+        let violations = files
+            .iter()
+            .filter_map(|path| self.violations.get(path))
+            .flatten()
+            .cloned()
+            .collect();
+        check_result_for_violations(&self.configuration, files, violations)
+    }
+}
+
+fn build_constant_resolver(
+    configuration: &Configuration,
+    processed_files: &HashMap<PathBuf, ProcessedFile>,
+) -> Box<dyn ConstantResolver + Send + Sync> {
+    if configuration.experimental_parser {
+        let processed_files: Vec<ProcessedFile> =
+            processed_files.values().cloned().collect();
+        get_experimental_constant_resolver(
+            &configuration.absolute_root,
+            &processed_files,
+            &configuration.ignored_definitions,
+        )
+    } else {
+        get_zeitwerk_constant_resolver(
+            &configuration.pack_set,
+            &configuration.constant_resolver_configuration(),
+        )
+    }
+}
+
+// Violations are compared by what they are about rather than where they
+// are, so editing lines above a violation doesn't report it as fixed and new
+fn violation_delta<'a>(
+    before: &'a HashSet<Violation>,
+    after: &'a HashSet<Violation>,
+) -> (Vec<&'a Violation>, Vec<&'a Violation>) {
+    let identifiers = |violations: &'a HashSet<Violation>| {
+        violations
+            .iter()
+            .map(|violation| &violation.identifier)
+            .collect::<HashSet<&ViolationIdentifier>>()
+    };
+    let before_identifiers = identifiers(before);
+    let after_identifiers = identifiers(after);
+    let new_violations = after
+        .iter()
+        .filter(|violation| !before_identifiers.contains(&violation.identifier))
+        .collect();
+    let fixed_violations = before
+        .iter()
+        .filter(|violation| !after_identifiers.contains(&violation.identifier))
+        .collect();
+    (new_violations, fixed_violations)
+}
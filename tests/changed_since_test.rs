@@ -0,0 +1,154 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+mod common;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args([
+            "-c",
+            "user.name=packs",
+            "-c",
+            "user.email=packs@example.com",
+        ])
+        .args(args)
+        // Git hooks point these at the repository being committed to
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_INDEX_FILE")
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+// Copies a fixture into a fresh git repository with a single commit
+fn set_up_git_repo(fixture: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("packs_changed_since_{}", fixture));
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures").join(fixture), &dir);
+    git(&dir, &["init", "--quiet"]);
+    git(&dir, &["add", "--all"]);
+    git(&dir, &["commit", "--quiet", "--message", "initial"]);
+    dir
+}
+
+fn packs_command(dir: &Path) -> Result<Command, Box<dyn Error>> {
+    let mut command = Command::cargo_bin("packs")?;
+    command
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_INDEX_FILE")
+        .arg("--project-root")
+        .arg(dir);
+    Ok(command)
+}
+
+#[test]
+fn test_check_changed_since_without_changes() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_git_repo("simple_app");
+
+    packs_command(&dir)?
+        .arg("--no-cache")
+        .arg("check")
+        .arg("--changed-since")
+        .arg("HEAD")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No violations detected!"));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_changed_since_with_modified_file() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_git_repo("contains_package_todo");
+    let foo_rb = dir.join("packs/foo/app/services/foo.rb");
+    let contents = fs::read_to_string(&foo_rb)?;
+    fs::write(&foo_rb, format!("{}\n", contents))?;
+
+    packs_command(&dir)?
+        .arg("--no-cache")
+        .arg("check")
+        .arg("--ignore-recorded-violations")
+        .arg("--changed-since")
+        .arg("HEAD")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/foo.rb:3:4\nDependency violation",
+        ))
+        .stdout(predicate::str::contains("other_foo.rb").not());
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_changed_since_with_untracked_file() -> Result<(), Box<dyn Error>>
+{
+    let dir = set_up_git_repo("contains_package_todo");
+    fs::copy(
+        dir.join("packs/foo/app/services/foo.rb"),
+        dir.join("packs/foo/app/services/new_foo.rb"),
+    )?;
+
+    packs_command(&dir)?
+        .arg("--no-cache")
+        .arg("check")
+        .arg("--changed-since")
+        .arg("HEAD")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/new_foo.rb:3:4\nDependency violation",
+        ));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_changed_since_with_deleted_file() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_git_repo("contains_package_todo");
+    fs::remove_file(dir.join("packs/foo/app/services/other_foo.rb"))?;
+
+    packs_command(&dir)?
+        .arg("--no-cache")
+        .arg("check")
+        .arg("--changed-since")
+        .arg("HEAD")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "There were stale violations found, please run `packs update`",
+        ));
+
+    fs::remove_dir_all(dir)?;
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_changed_since_with_unknown_ref() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_git_repo("simple_packs_first_app");
+
+    packs_command(&dir)?
+        .arg("--no-cache")
+        .arg("check")
+        .arg("--changed-since")
+        .arg("not-a-ref")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("git merge-base not-a-ref HEAD"));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
@@ -344,3 +344,235 @@ fn test_check_contents_ignoring_recorded_violations(
     common::teardown();
     Ok(())
 }
+
+#[test]
+fn test_check_with_json_format() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let result: serde_json::Value = serde_json::from_slice(&output)?;
+    let reportable_violations =
+        result["reportable_violations"].as_array().unwrap();
+    assert_eq!(reportable_violations.len(), 2);
+
+    let dependency_violation = &reportable_violations[0];
+    assert_eq!(dependency_violation["violation_type"], "dependency");
+    assert_eq!(dependency_violation["strict"], false);
+    assert_eq!(
+        dependency_violation["file"],
+        "packs/foo/app/services/foo.rb"
+    );
+    assert_eq!(dependency_violation["constant_name"], "::Bar");
+    assert_eq!(dependency_violation["referencing_pack_name"], "packs/foo");
+    assert_eq!(dependency_violation["defining_pack_name"], "packs/bar");
+    assert_eq!(dependency_violation["source_location"]["line"], 3);
+    assert_eq!(dependency_violation["source_location"]["column"], 4);
+    assert_eq!(
+        dependency_violation["defining_file"],
+        "packs/bar/app/services/bar.rb"
+    );
+    assert!(!dependency_violation["message"]
+        .as_str()
+        .unwrap()
+        .contains('\x1b'));
+    assert_eq!(reportable_violations[1]["violation_type"], "privacy");
+
+    assert_eq!(result["stale_violations"], serde_json::json!([]));
+    assert_eq!(result["strict_mode_violations"], serde_json::json!([]));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_json_format_and_stale_violations(
+) -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("check")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let result: serde_json::Value = serde_json::from_slice(&output)?;
+    let stale_violations = result["stale_violations"].as_array().unwrap();
+    assert_eq!(stale_violations.len(), 3);
+    assert_eq!(stale_violations[0]["file"], "packs/bar/app/services/bar.rb");
+    assert_eq!(stale_violations[0]["constant_name"], "::Foo");
+    assert!(stale_violations[0].get("source_location").is_none());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_sarif_format() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--format")
+        .arg("sarif")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let log: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "packs");
+    let rule_ids: Vec<&str> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect();
+    assert!(rule_ids.contains(&"packs/dependency"));
+    assert!(rule_ids.contains(&"packs/privacy"));
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    let dependency_result = &results[0];
+    assert_eq!(dependency_result["ruleId"], "packs/dependency");
+    assert_eq!(dependency_result["level"], "error");
+    assert!(dependency_result["message"]["text"]
+        .as_str()
+        .unwrap()
+        .starts_with("Dependency violation: `::Bar`"));
+    let physical_location =
+        &dependency_result["locations"][0]["physicalLocation"];
+    assert_eq!(
+        physical_location["artifactLocation"]["uri"],
+        "packs/foo/app/services/foo.rb"
+    );
+    assert_eq!(
+        physical_location["artifactLocation"]["uriBaseId"],
+        "%SRCROOT%"
+    );
+    assert_eq!(physical_location["region"]["startLine"], 3);
+    assert_eq!(physical_location["region"]["startColumn"], 5);
+    assert!(dependency_result.get("suppressions").is_none());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_sarif_format_and_package_todo_file(
+) -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_package_todo")
+        .arg("check")
+        .arg("--format")
+        .arg("sarif")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let log: serde_json::Value = serde_json::from_slice(&output)?;
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    for result in results {
+        assert_eq!(result["ruleId"], "packs/dependency");
+        assert_eq!(result["suppressions"][0]["kind"], "external");
+    }
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_without_tty_has_no_color() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains('\x1b').not());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_github_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--format")
+        .arg("github")
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with(
+            "::error file=packs/foo/app/services/foo.rb,line=3,col=5,title=packs dependency violation::Dependency violation: `::Bar` belongs to `packs/bar`",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_junit_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--format")
+        .arg("junit")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            r#"<testsuites name="packs" tests="2" failures="2">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<testcase name="packs/foo/app/services/foo.rb:3:4" classname="packs/foo/app/services/foo.rb" file="packs/foo/app/services/foo.rb">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<failure type="dependency" message="Dependency violation: `::Bar` belongs to `packs/bar`"#,
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_checkstyle_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("check")
+        .arg("--format")
+        .arg("checkstyle")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            r#"<file name="packs/foo/package_todo.yml">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<error severity="error" message="Stale dependency violation on `::Bar`"#,
+        ));
+
+    common::teardown();
+    Ok(())
+}
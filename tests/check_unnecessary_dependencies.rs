@@ -61,3 +61,19 @@ fn test_check_unnecessary_dependencies_no_issue() -> Result<(), Box<dyn Error>>
         .success();
     Ok(())
 }
+
+#[test]
+fn test_check_unnecessary_glob_dependency() -> Result<(), Box<dyn Error>> {
+    // `packs/platform/*` matches `packs/platform/foo` itself, whose own
+    // references don't make the glob necessary
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_unused_glob_dependency")
+        .arg("check-unnecessary-dependencies")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "packs/platform/foo depends on packs/platform/* but does not use it",
+        ));
+    Ok(())
+}
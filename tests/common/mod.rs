@@ -1,4 +1,12 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
 
 //
 // For more information about this file's naming convention, see
@@ -53,6 +61,24 @@ pub fn delete_new_app(dir_name: &str) {
     }
 }
 
+// Copies a fixture directory (without its tmp directory) so that tests can
+// mutate it freely
+#[allow(dead_code)]
+pub fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            if entry.file_name() != "tmp" {
+                copy_dir(&entry.path(), &target);
+            }
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
 // In case we want our tests to call `update` or otherwise mutate the file system
 #[allow(dead_code)]
 pub fn set_up_fixtures() {
@@ -169,3 +195,56 @@ enforce_dependencies: true
 
     fs::write(pack_yml, pack_yml_contents).unwrap();
 }
+
+// A long-running command (e.g. `check --watch`) whose output is read line by
+// line. It is killed when dropped, even if an assertion fails.
+#[allow(dead_code)]
+pub struct BackgroundProcess {
+    child: Child,
+    lines: Receiver<String>,
+}
+
+#[allow(dead_code)]
+impl BackgroundProcess {
+    pub fn spawn(command: &mut Command) -> BackgroundProcess {
+        let mut child = command.stdout(Stdio::piped()).spawn().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        BackgroundProcess { child, lines }
+    }
+
+    // Collects output lines until one contains `expected`
+    pub fn wait_for(&self, expected: &str) -> Vec<String> {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let mut lines = Vec::new();
+        while let Some(remaining) =
+            deadline.checked_duration_since(Instant::now())
+        {
+            match self.lines.recv_timeout(remaining) {
+                Ok(line) => {
+                    let found = line.contains(expected);
+                    lines.push(line);
+                    if found {
+                        return lines;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+        panic!("Timed out waiting for {:?}, got {:?}", expected, lines);
+    }
+}
+
+impl Drop for BackgroundProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
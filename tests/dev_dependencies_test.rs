@@ -0,0 +1,39 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check() -> Result<(), Box<dyn Error>> {
+    // packs/orders/spec/orders_spec.rb references `::Factories` too, which
+    // its dev dependency allows
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_dev_dependencies")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/orders/app/services/orders.rb:2:2\nDependency violation: `::Factories` belongs to `packs/factories`, but `packs/orders/package.yml` only specifies a dev dependency on `packs/factories`, which only test files may use.",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_spec_file() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_dev_dependencies")
+        .arg("check")
+        .arg("packs/orders/spec/orders_spec.rb")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No violations detected!"));
+
+    common::teardown();
+    Ok(())
+}
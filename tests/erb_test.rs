@@ -0,0 +1,71 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod common;
+
+const TEMPLATE: &str = "\
+<div>
+  <h1>Title</h1>
+  <p><%= ::Bar %></p>
+  <%# pks:disable-next-line privacy %>
+  <% ::Bar.each do |item| %>
+  <% end %>
+</div>
+";
+
+fn set_up_app(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("packs_erb_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures/simple_app"), &dir);
+    fs::write(
+        dir.join("packs/foo/app/services/foo.rb"),
+        "module Foo\nend\n",
+    )
+    .unwrap();
+    fs::write(dir.join("packs/foo/app/views/foo.erb"), TEMPLATE).unwrap();
+    dir
+}
+
+fn assert_erb_locations(
+    name: &str,
+    args: &[&str],
+) -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app(name);
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .args(args)
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("3 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/foo.erb:3:9\nDependency violation",
+        ))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/foo.erb:3:9\nPrivacy violation",
+        ))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/foo.erb:5:5\nDependency violation",
+        ))
+        .stdout(predicate::str::contains("foo.erb:5:5\nPrivacy").not());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_erb_locations() -> Result<(), Box<dyn Error>> {
+    assert_erb_locations("packwerk", &[])
+}
+
+#[test]
+fn test_erb_locations_with_experimental_parser() -> Result<(), Box<dyn Error>> {
+    assert_erb_locations("experimental", &["--experimental-parser"])
+}
@@ -0,0 +1,90 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+use std::{error::Error, path::Path, process::Command};
+
+mod common;
+
+#[test]
+#[serial]
+fn test_check_reports_expired_entries() -> Result<(), Box<dyn Error>> {
+    // The entry for `::Baz` doesn't expire until 2999
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_expiring_todos")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/foo.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`, but `packs/foo/package.yml` does not specify a dependency on `packs/bar`.\nIts package_todo.yml entry expired on 2020-01-31",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_todo_expiring() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_expiring_todos")
+        .arg("todo")
+        .arg("expiring")
+        .arg("--within")
+        .arg("30d")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1 package_todo.yml entries expire within 30 days:\npacks/foo/package_todo.yml: `::Bar` on `packs/bar` expired on 2020-01-31, owner: @payments-team, ticket: PAY-123\n",
+        ))
+        .stdout(predicate::str::contains("::Baz").not());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_todo_expiring_with_invalid_duration() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_expiring_todos")
+        .arg("todo")
+        .arg("expiring")
+        .arg("--within")
+        .arg("a month")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "`a month` is not a number of days (e.g. 30d) or weeks (e.g. 2w)",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_keeps_annotations() -> Result<(), Box<dyn Error>> {
+    let package_todo_yml_filepath = Path::new(
+        "tests/fixtures/app_with_expiring_todos/packs/foo/package_todo.yml",
+    );
+    let expected = std::fs::read_to_string(package_todo_yml_filepath)?;
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_expiring_todos")
+        .arg("update")
+        .assert()
+        .success();
+
+    let actual = std::fs::read_to_string(package_todo_yml_filepath)?;
+    std::fs::write(package_todo_yml_filepath, &expected)?;
+    assert_eq!(expected, actual);
+
+    common::teardown();
+    Ok(())
+}
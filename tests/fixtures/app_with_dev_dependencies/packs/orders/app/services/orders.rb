@@ -0,0 +1,3 @@
+module Orders
+  Factories
+end
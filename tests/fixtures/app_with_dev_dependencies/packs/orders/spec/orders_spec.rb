@@ -0,0 +1,3 @@
+describe Orders do
+  Factories
+end
@@ -0,0 +1,6 @@
+module Foo
+  def calls_bar_and_baz
+    Bar
+    Baz
+  end
+end
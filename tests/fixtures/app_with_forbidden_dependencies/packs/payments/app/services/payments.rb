@@ -0,0 +1,3 @@
+module Payments
+  Marketing
+end
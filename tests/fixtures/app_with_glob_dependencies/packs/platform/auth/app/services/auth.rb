@@ -0,0 +1,3 @@
+module Auth
+  Core
+end
@@ -0,0 +1,3 @@
+module Core
+  Auth
+end
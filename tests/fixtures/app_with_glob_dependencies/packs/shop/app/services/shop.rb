@@ -0,0 +1,4 @@
+module Shop
+  Core
+  Auth
+end
@@ -0,0 +1,6 @@
+module Billing
+  module Api
+    class Client
+    end
+  end
+end
@@ -0,0 +1,4 @@
+module Billing
+  class Invoice
+  end
+end
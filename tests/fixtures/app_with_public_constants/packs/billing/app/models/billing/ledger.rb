@@ -0,0 +1,4 @@
+module Billing
+  class Ledger
+  end
+end
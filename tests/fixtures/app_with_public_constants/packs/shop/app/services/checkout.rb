@@ -0,0 +1,7 @@
+class Checkout
+  def call
+    Billing::Invoice.new
+    Billing::Api::Client.new
+    Billing::Ledger.new
+  end
+end
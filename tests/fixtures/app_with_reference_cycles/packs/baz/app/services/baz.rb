@@ -0,0 +1,5 @@
+module Baz
+  def self.call
+    Foo.call
+  end
+end
@@ -0,0 +1,6 @@
+module Foo
+  def self.call
+    Bar.call
+    Bar.name
+  end
+end
@@ -0,0 +1,5 @@
+module Bar
+  def self.call
+    Baz.call
+  end
+end
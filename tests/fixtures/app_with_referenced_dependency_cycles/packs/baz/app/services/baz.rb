@@ -0,0 +1,6 @@
+module Baz
+  def self.call
+    Foo.call
+    Foo.name
+  end
+end
@@ -0,0 +1,7 @@
+module Foo
+  def self.call
+    Bar.call
+    Bar.name
+    Bar.to_s
+  end
+end
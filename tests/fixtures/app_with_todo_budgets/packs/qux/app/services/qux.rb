@@ -0,0 +1,5 @@
+module Qux
+  def calls_bar
+    Bar
+  end
+end
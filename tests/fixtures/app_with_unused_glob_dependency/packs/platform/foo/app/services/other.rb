@@ -0,0 +1,3 @@
+class Other
+  Foo
+end
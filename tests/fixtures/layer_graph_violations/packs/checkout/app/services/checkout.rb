@@ -0,0 +1,5 @@
+module Checkout
+  # The ui layer may use the domain layer, but not skip it
+  Orders
+  OrderRecords
+end
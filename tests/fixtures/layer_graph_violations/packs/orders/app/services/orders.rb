@@ -0,0 +1,3 @@
+module Orders
+  OrderRecords
+end
@@ -0,0 +1,39 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_forbidden_dependencies")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/payments/app/services/payments.rb:2:2\nForbidden dependency violation: `::Marketing` belongs to `packs/marketing`, which `packs/payments` must never depend on, see `packs/payments/**` -> `packs/marketing/**` (Payments must not know about campaigns) in `packwerk.yml`",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_forbidden_dependencies")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 validation error(s) detected:"))
+        .stdout(predicate::str::contains(
+            "Invalid 'dependencies' in 'packs/payments/package.yml'. Depending on `packs/marketing` is forbidden by `packs/payments/**` -> `packs/marketing/**` (Payments must not know about campaigns) in `packwerk.yml`",
+        ));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check() -> Result<(), Box<dyn Error>> {
+    // packs/shop depends on `packs/platform/*`, but packs/platform/core is
+    // only visible to `packs/platform/*`
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_glob_dependencies")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/shop/app/services/shop.rb:2:2\nVisibility violation: `::Core` belongs to `packs/platform/core`, which is not visible to `packs/shop`",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_glob_dependencies")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("2 validation error(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/shop/package.yml has 'packs/legacy/*' in its ignored_dependencies, but that glob matches no other pack.",
+        ))
+        .stdout(predicate::str::contains(
+            "- packs/platform/auth -> packs/platform/core (1) -> packs/platform/auth (1)",
+        ))
+        .stdout(predicate::str::contains(
+            "- packs/platform/auth -> packs/platform/core (1 reference, declared by `packs/platform/*`)",
+        ));
+
+    common::teardown();
+    Ok(())
+}
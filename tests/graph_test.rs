@@ -0,0 +1,110 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::process::Command;
+
+mod common;
+
+#[test]
+fn test_graph_defaults_to_dot() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("graph")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph packs {\n"))
+        .stdout(predicate::str::contains(
+            "  \"packs/foo\" -> \"packs/baz\";\n",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_graph_with_implicit_dependencies_as_json() -> Result<(), Box<dyn Error>>
+{
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_package_todo")
+        .arg("graph")
+        .arg("--implicit")
+        .arg("--format")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success());
+
+    let graph: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        graph["edges"],
+        json!([{
+            "from": "packs/foo",
+            "to": "packs/bar",
+            "declared": false,
+            "violations": {"dependency": 1}
+        }])
+    );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_graph_focus_as_mermaid() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("graph")
+        .arg("--focus")
+        .arg("packs/baz")
+        .arg("--depth")
+        .arg("1")
+        .arg("--format")
+        .arg("mermaid")
+        .assert()
+        .success()
+        .stdout(
+            "graph LR\n  n0[\"packs/baz\"]\n  n1[\"packs/foo\"]\n  n1 --> n0\n",
+        );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_graph_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--format")
+        .arg("dot")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'dot' for '--format <FORMAT>'",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_graph_with_reporter_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("graph")
+        .arg("--format")
+        .arg("sarif")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'sarif' for '--format <FORMAT>'",
+        ));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod common;
+
+fn set_up_app(name: &str, view: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("packs_templates_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures/simple_app"), &dir);
+    fs::write(
+        dir.join("packs/foo/app/services/foo.rb"),
+        "module Foo\nend\n",
+    )
+    .unwrap();
+    fs::write(dir.join("packs/foo/app/views").join(view), contents).unwrap();
+    dir
+}
+
+#[test]
+fn test_check_haml() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app(
+        "haml",
+        "show.html.haml",
+        "\
+%div
+  - if ::Bar.enabled?
+    %p= ::Bar.title
+  %p Hello #{::Bar.name}
+",
+    );
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/show.html.haml:2:7\nDependency violation: `::Bar`",
+        ))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/show.html.haml:3:8\nDependency violation: `::Bar`",
+        ))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/show.html.haml:4:13\nDependency violation: `::Bar`",
+        ));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_slim() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app(
+        "slim",
+        "show.html.slim",
+        "\
+div
+  - ::Bar.all.each do |bar|
+    p = bar.title
+  p Hello #{::Bar.name}
+",
+    );
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("--experimental-parser")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/show.html.slim:2:4\nDependency violation: `::Bar`",
+        ))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/views/show.html.slim:4:12\nDependency violation: `::Bar`",
+        ));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -39,3 +39,27 @@ fn test_check_enforce_layers_disabled() -> Result<(), Box<dyn Error>> {
     common::teardown();
     Ok(())
 }
+
+#[test]
+fn test_check_with_layer_graph() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/layer_graph_violations")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output =
+        String::from_utf8_lossy(&strip_ansi_escapes::strip(output)).to_string();
+
+    // packs/orders may use packs/order_records, but packs/checkout may not
+    // skip the domain layer
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/checkout/app/services/checkout.rb:4:2\nLayer violation: `::OrderRecords` belongs to `packs/order_records` (whose layer is `persistence`) cannot be accessed from `packs/checkout` (whose layer is `ui`)"));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use assert_cmd::cargo::CommandCargoExt;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{ChildStdin, ChildStdout, Command, Stdio};
+
+mod common;
+
+fn send(stdin: &mut ChildStdin, message: Value) {
+    let body = message.to_string();
+    write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+    stdin.flush().unwrap();
+}
+
+fn receive(stdout: &mut BufReader<ChildStdout>) -> Value {
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        stdout.read_line(&mut header).unwrap();
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(length) = header.strip_prefix("Content-Length: ") {
+            content_length = length.parse().unwrap();
+        }
+    }
+    let mut body = vec![0; content_length];
+    stdout.read_exact(&mut body).unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn receive_until(
+    stdout: &mut BufReader<ChildStdout>,
+    predicate: impl Fn(&Value) -> bool,
+) -> Value {
+    loop {
+        let message = receive(stdout);
+        if predicate(&message) {
+            return message;
+        }
+    }
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_and_code_actions(
+) -> Result<(), Box<dyn Error>> {
+    let project_root =
+        PathBuf::from("tests/fixtures/simple_app").canonicalize()?;
+    let mut child = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&project_root)
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    send(
+        &mut stdin,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}}),
+    );
+    let initialize = receive_until(&mut stdout, |m| m["id"] == 1);
+    assert_eq!(
+        initialize["result"]["capabilities"]["executeCommandProvider"]
+            ["commands"],
+        json!(["packs.addDependency"])
+    );
+    send(
+        &mut stdin,
+        json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+    );
+
+    let file = project_root.join("packs/foo/app/services/foo.rb");
+    let uri = format!("file://{}", file.display());
+    send(
+        &mut stdin,
+        json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+            "textDocument": {
+                "uri": uri,
+                "languageId": "ruby",
+                "version": 1,
+                "text": std::fs::read_to_string(&file)?,
+            }
+        }}),
+    );
+    let published = receive_until(&mut stdout, |m| {
+        m["method"] == "textDocument/publishDiagnostics"
+    });
+    let diagnostics = published["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    let dependency_diagnostic = diagnostics
+        .iter()
+        .find(|d| d["code"] == "dependency")
+        .unwrap();
+    assert_eq!(
+        dependency_diagnostic["range"],
+        json!({"start": {"line": 2, "character": 4}, "end": {"line": 2, "character": 9}})
+    );
+    assert!(dependency_diagnostic["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Dependency violation: `::Bar` belongs to `packs/bar`"));
+
+    // Unsaved edits are checked too
+    send(
+        &mut stdin,
+        json!({"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+            "textDocument": {"uri": uri, "version": 2},
+            "contentChanges": [{"text": "module Foo\nend\n"}],
+        }}),
+    );
+    let published = receive_until(&mut stdout, |m| {
+        m["method"] == "textDocument/publishDiagnostics"
+    });
+    assert_eq!(published["params"]["diagnostics"], json!([]));
+
+    send(
+        &mut stdin,
+        json!({"jsonrpc": "2.0", "id": 2, "method": "textDocument/codeAction", "params": {
+            "textDocument": {"uri": uri},
+            "range": dependency_diagnostic["range"],
+            "context": {"diagnostics": [dependency_diagnostic]},
+        }}),
+    );
+    let code_actions = receive_until(&mut stdout, |m| m["id"] == 2);
+    let code_action = &code_actions["result"][0];
+    assert_eq!(
+        code_action["title"],
+        "Add `packs/bar` to the dependencies of `packs/foo`"
+    );
+    assert_eq!(code_action["command"]["command"], "packs.addDependency");
+    assert_eq!(
+        code_action["command"]["arguments"],
+        json!(["packs/foo", "packs/bar"])
+    );
+
+    send(
+        &mut stdin,
+        json!({"jsonrpc": "2.0", "id": 3, "method": "shutdown"}),
+    );
+    receive_until(&mut stdout, |m| m["id"] == 3);
+    send(&mut stdin, json!({"jsonrpc": "2.0", "method": "exit"}));
+    assert!(child.wait()?.success());
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::process::Command;
+
+mod common;
+
+#[test]
+fn test_metrics_defaults_to_csv() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("metrics")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "\
+pack,afferent_coupling,efferent_coupling,instability,abstractness,distance_from_main_sequence,public_constants,constants
+.,0,0,,0,,0,2
+packs/bar,1,0,0,0,1,0,2
+packs/baz,1,0,0,0,1,0,1
+packs/foo,0,2,1,0,0,0,2
+",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_metrics_as_json() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_public_constants")
+        .arg("metrics")
+        .arg("--format")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success());
+
+    let metrics: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        metrics[1],
+        json!({
+            "pack": "packs/billing",
+            "afferent_coupling": 1,
+            "efferent_coupling": 0,
+            "instability": 0.0,
+            "abstractness": 0.6667,
+            "distance_from_main_sequence": 0.3333,
+            "public_constants": 2,
+            "constants": 3
+        })
+    );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_metrics_with_unsupported_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("metrics")
+        .arg("--format")
+        .arg("sarif")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'sarif' for '--format <FORMAT>'",
+        ));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod common;
+
+fn set_up_app(fixture: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("packs_move_{}", fixture));
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures").join(fixture), &dir);
+    dir
+}
+
+#[test]
+fn test_move_file_reports_dependency_changes() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("simple_app");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("move")
+        .arg("packs/foo/app/services/foo.rb")
+        .arg("packs/bar")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Moved packs/foo/app/services/foo.rb to packs/bar/app/services/foo.rb",
+        ))
+        .stdout(predicate::str::contains(
+            "`packs/bar` now references `packs/baz`, run `packs add-dependency packs/bar packs/baz` to add a dependency",
+        ))
+        .stdout(predicate::str::contains(
+            "`packs/foo` no longer references `packs/baz`, its dependency on `packs/baz` can be removed",
+        ));
+
+    assert!(!dir.join("packs/foo/app/services/foo.rb").exists());
+    assert!(dir.join("packs/bar/app/services/foo.rb").exists());
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_move_file_rewrites_package_todo() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("contains_package_todo");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("move")
+        .arg("packs/foo/app/services")
+        .arg("packs/bar")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Updated package_todo.yml for `packs/foo`",
+        ));
+
+    // References from the moved files to `packs/bar` are now internal
+    assert!(!dir.join("packs/foo/package_todo.yml").exists());
+    assert!(!dir.join("packs/bar/package_todo.yml").exists());
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_move_file_keeps_remaining_todo_entries() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("contains_package_todo");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("move")
+        .arg("packs/foo/app/services/other_foo.rb")
+        .arg("packs/bar")
+        .assert()
+        .success();
+
+    let package_todo =
+        fs::read_to_string(dir.join("packs/foo/package_todo.yml"))?;
+    assert!(package_todo.contains("- packs/foo/app/services/foo.rb"));
+    assert!(!package_todo.contains("other_foo.rb"));
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .assert()
+        .success();
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_move_file_reopening_a_namespace() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("contains_package_todo");
+    // `baz.rb` reopens `Bar`, which stays defined in `packs/bar`
+    fs::write(
+        dir.join("packs/bar/app/services/bar.rb"),
+        "module Bar\n  def self.call\n  end\nend\n",
+    )?;
+    fs::create_dir_all(dir.join("packs/bar/app/services/bar"))?;
+    fs::write(
+        dir.join("packs/bar/app/services/bar/baz.rb"),
+        "module Bar\n  def self.baz\n  end\nend\n",
+    )?;
+    fs::create_dir_all(dir.join("packs/baz"))?;
+    fs::write(dir.join("packs/baz/package.yml"), "")?;
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("--experimental-parser")
+        .arg("move")
+        .arg("packs/bar/app/services/bar/baz.rb")
+        .arg("packs/baz")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated package_todo.yml").not());
+
+    let package_todo =
+        fs::read_to_string(dir.join("packs/foo/package_todo.yml"))?;
+    assert!(package_todo.contains("packs/bar:\n  \"::Bar\":"));
+    assert!(!package_todo.contains("packs/baz"));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_move_files_rolls_back_on_failure() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("contains_package_todo");
+    fs::create_dir_all(dir.join("packs/foo/app/services/zzz"))?;
+    fs::write(
+        dir.join("packs/foo/app/services/zzz/thing.rb"),
+        "module Zzz\nend\n",
+    )?;
+    // A file where `zzz/thing.rb` needs a directory, so it can't be moved
+    // after the other files already have been
+    fs::create_dir_all(dir.join("packs/bar/app/services"))?;
+    fs::write(dir.join("packs/bar/app/services/zzz"), "")?;
+    let package_todo_yml = dir.join("packs/foo/package_todo.yml");
+    let package_todo = fs::read_to_string(&package_todo_yml)?;
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("move")
+        .arg("packs/foo/app/services")
+        .arg("packs/bar")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Moved").not())
+        .stderr(predicate::str::contains("No files were moved"));
+
+    assert!(dir.join("packs/foo/app/services/foo.rb").exists());
+    assert!(dir.join("packs/foo/app/services/other_foo.rb").exists());
+    assert!(!dir.join("packs/bar/app/services/foo.rb").exists());
+    assert_eq!(fs::read_to_string(&package_todo_yml)?, package_todo);
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_move_file_with_glob_dependencies() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("app_with_glob_dependencies");
+
+    // `packs/platform/core` depends on `packs/platform/auth` through
+    // `packs/platform/*`, which `packs/shop` also uses
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("move")
+        .arg("packs/shop/app/services/shop.rb")
+        .arg("packs/platform/core")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Moved packs/shop/app/services/shop.rb to packs/platform/core/app/services/shop.rb",
+        ))
+        .stdout(predicate::str::contains("add-dependency").not())
+        .stdout(predicate::str::contains("can be removed").not());
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_move_file_into_its_own_pack() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("simple_packs_first_app");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("move")
+        .arg("packs/foo/app/services/foo.rb")
+        .arg("packs/foo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "`packs/foo/app/services/foo.rb` already belongs to `packs/foo`",
+        ));
+
+    fs::remove_dir_all(dir)?;
+    common::teardown();
+    Ok(())
+}
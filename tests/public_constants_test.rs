@@ -0,0 +1,42 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check() -> Result<(), Box<dyn Error>> {
+    // `::Billing::Invoice` and `::Billing::Api::Client` are listed in
+    // `public_constants`, `::Billing::Ledger` isn't
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_public_constants")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/shop/app/services/checkout.rb:5:4\nPrivacy violation: `::Billing::Ledger` is private to `packs/billing`, but referenced from `packs/shop`",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_public_constants")
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 validation warning(s):"))
+        .stdout(predicate::str::contains(
+            "'public_constants' in 'packs/billing/package.yml': `::Billing::Refund` does not match any constant defined in `packs/billing`",
+        ))
+        .stdout(predicate::str::contains("Packwerk validate succeeded!"));
+
+    common::teardown();
+    Ok(())
+}
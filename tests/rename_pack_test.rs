@@ -0,0 +1,141 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod common;
+
+fn set_up_app(fixture: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("packs_rename_{}", fixture));
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures").join(fixture), &dir);
+    dir
+}
+
+#[test]
+fn test_rename_pack_updates_dependencies() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("simple_app");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("rename-pack")
+        .arg("packs/baz")
+        .arg("packs/core/qux")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed `packs/baz` to `packs/core/qux`",
+        ))
+        .stdout(predicate::str::contains("Updated packs/foo/package.yml"));
+
+    assert!(!dir.join("packs/baz").exists());
+    assert!(dir.join("packs/core/qux/app/services/baz.rb").exists());
+    let foo_yml = fs::read_to_string(dir.join("packs/foo/package.yml"))?;
+    assert!(foo_yml.contains("- packs/core/qux"));
+    assert!(!foo_yml.contains("packs/baz"));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_rename_pack_updates_package_todo() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("contains_package_todo");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("rename-pack")
+        .arg("packs/bar")
+        .arg("packs/barn")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Updated package_todo.yml for `packs/foo`",
+        ));
+
+    let package_todo =
+        fs::read_to_string(dir.join("packs/foo/package_todo.yml"))?;
+    assert!(package_todo.contains("\npacks/barn:\n"));
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No violations detected!"));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_rename_pack_renames_todo_header() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("contains_package_todo");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("rename-pack")
+        .arg("packs/foo")
+        .arg("packs/food")
+        .assert()
+        .success();
+
+    let package_todo =
+        fs::read_to_string(dir.join("packs/food/package_todo.yml"))?;
+    assert!(package_todo.contains("# 'packs/food' package."));
+    assert!(package_todo.contains("- packs/food/app/services/foo.rb"));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_rename_pack_with_automatic_namespace() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("app_with_automatic_namespaces");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("rename-pack")
+        .arg("packs/foo")
+        .arg("packs/widgets")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Constants in `packs/widgets` are now expected in `::Widgets` instead of `::Foo`",
+        ));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_rename_pack_to_existing_directory() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app("simple_packs_first_app");
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("rename-pack")
+        .arg("packs/foo")
+        .arg("packs/bar")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`packs/bar` already exists"));
+
+    fs::remove_dir_all(dir)?;
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+mod common;
+
+fn request(socket_path: &Path, request: &str) -> serde_json::Value {
+    let mut stream = UnixStream::connect(socket_path).unwrap();
+    writeln!(stream, "{}", request).unwrap();
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line).unwrap();
+    serde_json::from_str(&line).unwrap()
+}
+
+#[test]
+fn test_server() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join("packs_server");
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures/simple_app"), &dir);
+    let dir = dir.canonicalize()?;
+    // Keeps the sockets of this test apart from those of a real server
+    let runtime_dir = std::env::temp_dir().join("packs_server_runtime");
+    let _ = fs::remove_dir_all(&runtime_dir);
+    fs::create_dir_all(&runtime_dir)?;
+
+    let server = common::BackgroundProcess::spawn(
+        Command::cargo_bin("packs")?
+            .env("XDG_RUNTIME_DIR", &runtime_dir)
+            .arg("--project-root")
+            .arg(&dir)
+            .arg("--no-cache")
+            .arg("server"),
+    );
+    let lines = server.wait_for("Listening on ");
+    let socket_path = PathBuf::from(
+        lines.last().unwrap().strip_prefix("Listening on ").unwrap(),
+    );
+    let socket_dir = socket_path.parent().unwrap();
+    assert_eq!(socket_dir, runtime_dir.join("pks"));
+    assert_eq!(
+        fs::metadata(socket_dir)?.permissions().mode() & 0o777,
+        0o700
+    );
+
+    Command::cargo_bin("packs")?
+        .env("XDG_RUNTIME_DIR", &runtime_dir)
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--debug")
+        .arg("check")
+        .arg("packs/foo/app/services/foo.rb")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Using the server listening on"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/foo.rb:3:4\nDependency violation: `::Bar`",
+        ));
+
+    // Saved files are read again when they're checked
+    fs::write(
+        dir.join("packs/foo/app/services/foo.rb"),
+        "module Foo\nend\n",
+    )?;
+    Command::cargo_bin("packs")?
+        .env("XDG_RUNTIME_DIR", &runtime_dir)
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("check")
+        .arg("packs/foo/app/services/foo.rb")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No violations detected!"));
+
+    assert_cmd::Command::cargo_bin("packs")?
+        .env("XDG_RUNTIME_DIR", &runtime_dir)
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("check-contents")
+        .arg("--format")
+        .arg("json")
+        .arg("packs/foo/app/services/foo.rb")
+        .write_stdin("module Foo\n  ::Bar\nend\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"constant_name\": \"::Bar\""));
+
+    let response = request(&socket_path, r#"{"method": "list_packs"}"#);
+    let pack_names: Vec<&str> = response["packs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|pack| pack["name"].as_str().unwrap())
+        .collect();
+    assert!(pack_names.contains(&"packs/foo"));
+
+    let response = request(
+        &socket_path,
+        r#"{"method": "resolve_constant", "constant": "Bar", "namespace": ["Foo"]}"#,
+    );
+    assert_eq!(
+        response,
+        serde_json::json!({
+            "result": "constant",
+            "definitions": [{
+                "fully_qualified_name": "::Foo::Bar",
+                "path": "packs/foo/app/services/foo/bar.rb",
+                "pack": "packs/foo",
+            }],
+        })
+    );
+
+    let response = request(&socket_path, r#"{"method": "unknown"}"#);
+    assert_eq!(response["result"], "error");
+
+    // Other users could have replaced the socket
+    fs::set_permissions(socket_dir, fs::Permissions::from_mode(0o777))?;
+    Command::cargo_bin("packs")?
+        .env("XDG_RUNTIME_DIR", &runtime_dir)
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--debug")
+        .arg("check")
+        .arg("packs/bar/app/services/bar.rb")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("other users can access it"))
+        .stderr(predicate::str::contains("Using the server").not());
+
+    drop(server);
+    fs::remove_dir_all(&runtime_dir)?;
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_without_server() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("--debug")
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Using the server").not())
+        .stdout(predicate::str::contains("violation(s) detected"));
+
+    common::teardown();
+    Ok(())
+}
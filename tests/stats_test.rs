@@ -0,0 +1,176 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+mod common;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args([
+            "-c",
+            "user.name=packs",
+            "-c",
+            "user.email=packs@example.com",
+        ])
+        .args(args)
+        // Git hooks point these at the repository being committed to
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_INDEX_FILE")
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_stats() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_todo_budgets")
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "3 recorded violation(s)\n\nPack       Violations\npacks/foo  3\n",
+        ))
+        .stdout(predicate::str::contains(
+            "Defining pack  Violations\npacks/baz      2\npacks/bar      1\n",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_stats_with_reporter_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_todo_budgets")
+        .arg("stats")
+        .arg("--format")
+        .arg("sarif")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'sarif' for '--format <FORMAT>'",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_stats_with_git_history() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join("packs_stats_git_history");
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(
+        &PathBuf::from("tests/fixtures/app_with_todo_budgets"),
+        &dir,
+    );
+    git(&dir, &["init", "--quiet"]);
+    git(&dir, &["add", "--all"]);
+    git(&dir, &["commit", "--quiet", "--message", "initial"]);
+    let package_todo_yml = dir.join("packs/foo/package_todo.yml");
+    let contents = fs::read_to_string(&package_todo_yml)?;
+    fs::write(
+        &package_todo_yml,
+        contents.replace("    - packs/foo/app/services/removed.rb\n", ""),
+    )?;
+    git(
+        &dir,
+        &["commit", "--quiet", "--all", "--message", "burn down"],
+    );
+
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("stats")
+        .arg("--format")
+        .arg("json")
+        .arg("--git-history")
+        .arg("5")
+        .output()?;
+    assert!(output.status.success());
+
+    let stats: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(stats["total"], json!(2));
+    let history: Vec<Value> = stats["history"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| json!([entry["total"], entry["by_pack"]]))
+        .collect();
+    assert_eq!(
+        history,
+        vec![json!([3, {"packs/foo": 3}]), json!([2, {"packs/foo": 2}])]
+    );
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_with_git_history_follows_first_parent(
+) -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join("packs_stats_git_history_merge");
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(
+        &PathBuf::from("tests/fixtures/app_with_todo_budgets"),
+        &dir,
+    );
+    git(&dir, &["init", "--quiet", "--initial-branch", "main"]);
+    git(&dir, &["add", "--all"]);
+    git(&dir, &["commit", "--quiet", "--message", "initial"]);
+    git(&dir, &["checkout", "--quiet", "-b", "burn-down"]);
+    let package_todo_yml = dir.join("packs/foo/package_todo.yml");
+    let contents = fs::read_to_string(&package_todo_yml)?;
+    fs::write(
+        &package_todo_yml,
+        contents.replace("    - packs/foo/app/services/removed.rb\n", ""),
+    )?;
+    git(
+        &dir,
+        &["commit", "--quiet", "--all", "--message", "burn down"],
+    );
+    git(&dir, &["checkout", "--quiet", "main"]);
+    git(
+        &dir,
+        &[
+            "merge",
+            "--quiet",
+            "--no-ff",
+            "--message",
+            "merge burn-down",
+            "burn-down",
+        ],
+    );
+
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("stats")
+        .arg("--format")
+        .arg("json")
+        .arg("--git-history")
+        .arg("5")
+        .output()?;
+    assert!(output.status.success());
+
+    // The branch's commit is skipped, the merge commit stands for it
+    let stats: Value = serde_json::from_slice(&output.stdout)?;
+    let history: Vec<Value> = stats["history"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["total"].clone())
+        .collect();
+    assert_eq!(history, vec![json!(3), json!(2)]);
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod common;
+
+fn set_up_app(name: &str, foo_contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("packs_suppression_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures/simple_app"), &dir);
+    fs::write(dir.join("packs/foo/app/services/foo.rb"), foo_contents).unwrap();
+    dir
+}
+
+#[test]
+fn test_disable_next_line() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app(
+        "disable_next_line",
+        "\
+module Foo
+  def calls_bar_without_a_stated_dependency
+    # pks:disable-next-line dependency -- migrating in #123
+    ::Bar
+  end
+end
+",
+    );
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains("Privacy violation"))
+        .stdout(predicate::str::contains("Dependency violation").not());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_disable_next_line_multiple_types() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app(
+        "multiple_types",
+        "\
+module Foo
+  def calls_bar_without_a_stated_dependency
+    # pks:disable-next-line dependency, privacy
+    ::Bar
+  end
+end
+",
+    );
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No violations detected!"));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_report_unused_disables() -> Result<(), Box<dyn Error>> {
+    let dir = set_up_app(
+        "unused",
+        "\
+module Foo
+  def calls_bar_without_a_stated_dependency
+    # pks:disable-next-line dependency, privacy
+    ::Bar
+  end
+
+  def calls_baz_with_a_stated_dependency
+    # pks:disable-next-line dependency
+    Baz
+  end
+end
+",
+    );
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .assert()
+        .success();
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg(&dir)
+        .arg("--no-cache")
+        .arg("check")
+        .arg("--report-unused-disables")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "1 unused suppression(s) detected:",
+        ))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/foo.rb:8\nUnused suppression: no dependency violation on the line after `pks:disable-next-line dependency`",
+        ));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
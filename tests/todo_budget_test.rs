@@ -0,0 +1,87 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+use std::{error::Error, path::Path, process::Command};
+
+mod common;
+
+#[test]
+#[serial]
+fn test_check_reports_exceeded_budget() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_todo_budgets")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "packs/foo/package_todo.yml\n`packs/foo` records 3 dependency violation(s) in package_todo.yml, but its `max_todo_violations` allows 1",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_files_reports_budgets_of_their_packs_only(
+) -> Result<(), Box<dyn Error>> {
+    // `packs/bar` has no budget, so `packs/foo`'s doesn't concern it
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_todo_budgets")
+        .arg("check")
+        .arg("packs/bar/app/services/bar.rb")
+        .assert()
+        .stdout(predicate::str::contains("max_todo_violations").not());
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_todo_budgets")
+        .arg("check")
+        .arg("packs/foo/app/services/foo.rb")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "`packs/foo` records 3 dependency violation(s) in package_todo.yml, but its `max_todo_violations` allows 1",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_with_todo_ratchet() -> Result<(), Box<dyn Error>> {
+    let package_todo_yml_filepath = Path::new(
+        "tests/fixtures/app_with_todo_budgets/packs/foo/package_todo.yml",
+    );
+    let original = std::fs::read_to_string(package_todo_yml_filepath)?;
+
+    let assert = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_todo_budgets")
+        .arg("update")
+        .assert();
+    let actual = std::fs::read_to_string(package_todo_yml_filepath)?;
+    std::fs::write(package_todo_yml_filepath, &original)?;
+
+    assert
+        .failure()
+        .stdout(predicate::str::contains(
+            "1 new violation(s) were not recorded because `todo_ratchet` is enabled in packwerk.yml:\npacks/qux/app/services/qux.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`",
+        ));
+    // The stale entry is removed, but the new violation isn't recorded
+    assert_eq!(
+        original.replace("    - packs/foo/app/services/removed.rb\n", ""),
+        actual
+    );
+    assert!(!Path::new(
+        "tests/fixtures/app_with_todo_budgets/packs/qux/package_todo.yml"
+    )
+    .exists());
+
+    common::teardown();
+    Ok(())
+}
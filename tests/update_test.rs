@@ -96,6 +96,38 @@ packs/bar:
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_update_with_json_format() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("update")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let package_todo_yml_filepath =
+        Path::new("tests/fixtures/simple_app/packs/foo/package_todo.yml");
+    assert!(package_todo_yml_filepath.exists());
+    std::fs::remove_file(package_todo_yml_filepath)?;
+
+    let result: serde_json::Value = serde_json::from_slice(&output)?;
+    let recorded_violations = result["recorded_violations"].as_array().unwrap();
+    assert_eq!(recorded_violations.len(), 2);
+    assert_eq!(recorded_violations[0]["violation_type"], "dependency");
+    assert_eq!(recorded_violations[1]["violation_type"], "privacy");
+    assert_eq!(result["strict_mode_violations"], serde_json::json!([]));
+
+    common::teardown();
+
+    Ok(())
+}
+
 #[test]
 fn test_update_with_stale_violations() -> Result<(), Box<dyn Error>> {
     common::set_up_fixtures();
@@ -32,6 +32,54 @@ packs/foo, packs/bar",
     Ok(())
 }
 
+#[test]
+fn test_validate_cycle_with_references() -> Result<(), Box<dyn Error>> {
+    let expected_message = String::from(
+        "
+packs/foo, packs/baz, packs/bar
+Cycles, with the number of references backing each dependency:
+- packs/bar -> packs/foo (0) -> packs/bar (3)
+- packs/bar -> packs/baz (1) -> packs/foo (2) -> packs/bar (3)
+Removing these dependencies breaks every cycle:
+- packs/bar -> packs/baz (1 reference)
+- packs/bar -> packs/foo (0 references)",
+    );
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_referenced_dependency_cycles")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 validation error(s) detected:"))
+        .stdout(predicate::str::contains(expected_message));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate_reference_cycle() -> Result<(), Box<dyn Error>> {
+    // The dependencies are acyclic, but packs/bar references packs/foo
+    // through a recorded violation
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/app_with_reference_cycles")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 validation error(s) detected:"))
+        .stdout(predicate::str::contains(
+            "Found 1 reference cycle(s) involving packs with `enforce_acyclic_references`:",
+        ))
+        .stdout(predicate::str::contains(
+            "- packs/bar -> packs/foo (1) -> packs/bar (2)",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
 #[test]
 fn test_validate_layer() -> Result<(), Box<dyn Error>> {
     let expected_message_1 = String::from(
@@ -0,0 +1,43 @@
+use assert_cmd::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod common;
+
+#[test]
+fn test_check_watch() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join("packs_watch");
+    let _ = fs::remove_dir_all(&dir);
+    common::copy_dir(&PathBuf::from("tests/fixtures/simple_app"), &dir);
+    let foo_service = dir.join("packs/foo/app/services/foo.rb");
+
+    let watch_process = common::BackgroundProcess::spawn(
+        Command::cargo_bin("packs")?
+            .arg("--project-root")
+            .arg(&dir)
+            .arg("--no-cache")
+            .arg("check")
+            .arg("--watch"),
+    );
+    let lines = watch_process.wait_for("Watching for changes...");
+    assert!(lines.contains(&"2 violation(s) detected:".to_string()));
+
+    fs::write(&foo_service, "module Foo\nend\n")?;
+    let lines = watch_process.wait_for("fixed violation(s):");
+    assert_eq!(lines.last().unwrap(), "2 fixed violation(s):");
+    let lines = watch_process.wait_for("Dependency violation: `::Bar`");
+    assert_eq!(lines[0], "packs/foo/app/services/foo.rb:3:4");
+
+    fs::write(
+        &foo_service,
+        "module Foo\n  def bar\n    ::Bar\n  end\nend\n",
+    )?;
+    let lines = watch_process.wait_for("new violation(s):");
+    assert_eq!(lines.last().unwrap(), "2 new violation(s):");
+
+    drop(watch_process);
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}